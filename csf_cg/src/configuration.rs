@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::error::Error;
 use std::fmt;
 use std::path::PathBuf;
@@ -5,7 +6,7 @@ use structopt::StructOpt;
 
 pub type BoxResult<T> = Result<T, Box<dyn Error>>;
 
-#[derive(Debug, StructOpt)]
+#[derive(Debug, Clone, StructOpt, Serialize)]
 #[structopt(
     name = "Create_single_file_codingame_CLI",
     about = "Command Line Options for create_codingame_single_file",
@@ -35,6 +36,10 @@ pub struct Cli {
     #[structopt(short, long, default_value = "")]
     pub block_hidden: String,
 
+    /// block specific modules seperated by ";" from lib (default ""), even if they are used by challenge. Module name is same name you use with "use" command in Rust.
+    #[structopt(long, default_value = "")]
+    pub block_modules: String,
+
     /// name of local lib with locale modules. Same name as used in toml file.
     #[structopt(short, long, default_value = "my_lib")]
     pub lib: String,
@@ -43,7 +48,7 @@ pub struct Cli {
     #[structopt(short, long)]
     pub verbose: bool,
 
-    /// simulate execution without creating output; this option automatically set's verbose option
+    /// simulate execution without creating output or tmp files, printing a summary of fused modules; this option automatically set's verbose option
     #[structopt(short, long)]
     pub simulate: bool,
 
@@ -54,6 +59,100 @@ pub struct Cli {
     /// do not delete empty lines
     #[structopt(short, long)]
     pub keep_empty_lines: bool,
+
+    /// replace messages of panic!, unreachable! and assert macros with "" to shrink output; output is printed
+    /// again with prettyplease, which removes all comments except doc comments and all file markers
+    #[structopt(long)]
+    pub strip_panic_messages: bool,
+
+    /// fail if final cargo check of output still reports warnings
+    #[structopt(long)]
+    pub deny_warnings: bool,
+
+    /// line ending of output: "lf" or "crlf" (default: same as main.rs)
+    #[structopt(long, possible_values = &["lf", "crlf"])]
+    pub line_ending: Option<String>,
+
+    /// add a header comment to output listing fused crates and their versions
+    #[structopt(long)]
+    pub version_header: bool,
+
+    /// keep visibility modifiers like "pub" of loaded code instead of removing them
+    #[structopt(long)]
+    pub keep_visibility: bool,
+
+    /// only output required modules of local lib without challenge code; unused code is not filtered
+    #[structopt(long, conflicts_with = "challenge-only")]
+    pub libs_only: bool,
+
+    /// group consecutive use statements with same path into one use statement
+    #[structopt(long)]
+    pub group_uses: bool,
+
+    /// remove file markers from output; output cannot be updated with insert options afterwards
+    #[structopt(long)]
+    pub no_marker: bool,
+
+    /// add "rust-script" shebang and cargo manifest with required external crates to output
+    #[structopt(long)]
+    pub script_shebang: bool,
+
+    /// tool to detect unused code: "check" or "clippy"; clippy additionally purges items like empty Drop impls
+    #[structopt(long, default_value = "check", possible_values = &["check", "clippy"])]
+    pub purge_with: String,
+
+    /// file listing module names (one per line) in desired output order of module files; unlisted modules follow
+    /// in default order. Items inside a module file keep their order.
+    #[structopt(long, parse(from_os_str))]
+    pub module_order_file: Option<PathBuf>,
+
+    /// write output to stdout, even if output file is given; progress messages are written to stderr
+    #[structopt(long)]
+    pub stdout: bool,
+
+    /// print duration of each processing stage
+    #[structopt(long)]
+    pub stats: bool,
+
+    /// warn if output exceeds given number of characters (CodinGame accepts at most 100000)
+    #[structopt(long, default_value = "100000")]
+    pub size_limit: usize,
+
+    /// print each item of output on one line without indentation; removes comments and file markers
+    #[structopt(long)]
+    pub minify: bool,
+
+    /// keep doc comments when deleting comments: "all" (/// and //!) or "crate" (only //!)
+    #[structopt(long, possible_values = &["all", "crate"])]
+    pub keep_doc_comments: Option<String>,
+
+    /// fail if crate or lib depend on crates, which are not supported by CodinGame
+    #[structopt(long)]
+    pub strict_platform: bool,
+
+    /// name tmp dir and tmp files by hash of crate name and bin name instead of random UUID
+    #[structopt(long)]
+    pub deterministic_temp: bool,
+
+    /// additional supported external crates separated by ";"; extends [platform] supported_crates of csf_cg_config.toml in crate dir
+    #[structopt(long, default_value = "")]
+    pub supported_crates: String,
+
+    /// debug over-inclusion: stop filtering unused code after given number of cargo check rounds and print items, which would be purged next
+    #[structopt(long)]
+    pub purge_rounds: Option<usize>,
+
+    /// fuse binary target with given name; input is then path of package dir or its Cargo.toml
+    #[structopt(long)]
+    pub bin: Option<String>,
+
+    /// write JSON report of all code purged while filtering unused code to given path
+    #[structopt(long, parse(from_os_str))]
+    pub purge_report: Option<PathBuf>,
+
+    /// print effective configuration as TOML and exit
+    #[structopt(long)]
+    pub print_config: bool,
 }
 
 impl fmt::Display for Cli {
@@ -63,9 +162,54 @@ impl fmt::Display for Cli {
         writeln!(f, "challenge_only: {}", self.challenge_only)?;
         writeln!(f, "modules: {}", self.modules)?;
         writeln!(f, "block_hidden: {}", self.block_hidden)?;
+        writeln!(f, "block_modules: {}", self.block_modules)?;
         writeln!(f, "lib: {}", self.lib)?;
         writeln!(f, "verbose: {}", self.verbose)?;
-        writeln!(f, "simulate: {}", self.simulate)
+        writeln!(f, "simulate: {}", self.simulate)?;
+        writeln!(f, "strip_panic_messages: {}", self.strip_panic_messages)?;
+        writeln!(f, "deny_warnings: {}", self.deny_warnings)?;
+        writeln!(f, "line_ending: {:?}", self.line_ending)?;
+        writeln!(f, "version_header: {}", self.version_header)?;
+        writeln!(f, "keep_visibility: {}", self.keep_visibility)?;
+        writeln!(f, "libs_only: {}", self.libs_only)?;
+        writeln!(f, "group_uses: {}", self.group_uses)?;
+        writeln!(f, "no_marker: {}", self.no_marker)?;
+        writeln!(f, "script_shebang: {}", self.script_shebang)?;
+        writeln!(f, "purge_with: {}", self.purge_with)?;
+        writeln!(f, "module_order_file: {:?}", self.module_order_file)?;
+        writeln!(f, "stdout: {}", self.stdout)?;
+        writeln!(f, "stats: {}", self.stats)?;
+        writeln!(f, "size_limit: {}", self.size_limit)?;
+        writeln!(f, "minify: {}", self.minify)?;
+        writeln!(f, "keep_doc_comments: {:?}", self.keep_doc_comments)?;
+        writeln!(f, "strict_platform: {}", self.strict_platform)?;
+        writeln!(f, "deterministic_temp: {}", self.deterministic_temp)?;
+        writeln!(f, "supported_crates: {}", self.supported_crates)?;
+        writeln!(f, "purge_rounds: {:?}", self.purge_rounds)?;
+        writeln!(f, "bin: {:?}", self.bin)?;
+        writeln!(f, "purge_report: {:?}", self.purge_report)?;
+        writeln!(f, "print_config: {}", self.print_config)
+    }
+}
+
+impl Cli {
+    /// Effective configuration as TOML with all options; supported_crates lists
+    /// given crates merged with crates of CodinGame and csf_cg_config.toml.
+    pub fn to_toml(&self, supported_crates: &[String]) -> BoxResult<String> {
+        let mut config = toml::Table::try_from(self)?;
+        config.insert(
+            "supported_crates".into(),
+            supported_crates
+                .iter()
+                .map(|c| toml::Value::from(c.as_str()))
+                .collect::<Vec<_>>()
+                .into(),
+        );
+        Ok(config.to_string())
+    }
+    /// fused output is written to stdout, if requested or if no output file is given
+    pub fn output_to_stdout(&self) -> bool {
+        self.stdout || self.output.is_none()
     }
 }
 
@@ -75,17 +219,26 @@ pub enum CGError {
     MustProvideOutPutFile,
     PackageStructureError(PathBuf),
     OutputFileError(PathBuf),
+    ForeignTmpDir(PathBuf),
     NoStartLine(usize),
     NoEndLine,
     TooManyClosingBrackets,
     CouldNotFindEnumName,
+    RemainingWarnings(Vec<String>),
+    MissingToolchain(String),
+    MissingMarker(String),
+    UnsupportedProcMacro(String, String, usize),
+    UnsupportedDependencies(Vec<String>),
+    BlockedModuleRequired(String),
+    BinaryNotFound(String, Vec<String>),
+    UnsupportedEdition(String),
 }
 
 impl fmt::Display for CGError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::MustProvideInPutFile => {
-                write!(f, "No input file main.rs specified!")
+                write!(f, "No input file main.rs or binary file in src/bin specified!")
             }
             Self::MustProvideOutPutFile => {
                 write!(f, "No output file specified with active insert options!")
@@ -100,6 +253,11 @@ impl fmt::Display for CGError {
                 "output path \"{:?}\" does not point to /src/bin dir in crate directory",
                 path
             ),
+            Self::ForeignTmpDir(path) => write!(
+                f,
+                "tmp dir \"{:?}\" already exists, but was not created by csf_cg; remove or rename it",
+                path
+            ),
             Self::NoStartLine(message_line) => write!(
                 f,
                 "Could not find start line of name space for message line {}",
@@ -113,8 +271,150 @@ impl fmt::Display for CGError {
             Self::CouldNotFindEnumName => {
                 write!(f, "Could not find enum name of never constructed variant")
             }
+            Self::RemainingWarnings(messages) => write!(
+                f,
+                "cargo check of output reports {} warning(s):\n{}",
+                messages.len(),
+                messages.join("\n")
+            ),
+            Self::MissingToolchain(tool) => write!(
+                f,
+                "could not execute \"{} --version\"; please make sure {} is installed and in PATH",
+                tool, tool
+            ),
+            Self::UnsupportedProcMacro(name, file, line) => write!(
+                f,
+                "unsupported procedural macro \"{}!\" in {} (line {} of output); only macros defined with macro_rules! can be fused",
+                name, file, line
+            ),
+            Self::UnsupportedDependencies(dependencies) => write!(
+                f,
+                "dependencies not supported by CodinGame: {}; remove them or run without --strict-platform",
+                dependencies.join(", ")
+            ),
+            Self::BlockedModuleRequired(module) => write!(
+                f,
+                "blocked module \"{}\" is required by fused output; remove it from --block-modules",
+                module
+            ),
+            Self::BinaryNotFound(bin, available_bins) => write!(
+                f,
+                "binary target \"{}\" not found; available binary targets: {}",
+                bin,
+                available_bins.join(", ")
+            ),
+            Self::UnsupportedEdition(package) => write!(
+                f,
+                "package \"{}\" uses edition 2015, which is not supported; set edition to 2018 or later",
+                package
+            ),
+            Self::MissingMarker(marker) => write!(
+                f,
+                "could not find marker \"{}\" in output; output created with --no-marker cannot be updated",
+                marker
+            ),
         }
     }
 }
 
 impl Error for CGError {}
+
+/// Options of a plain run on input with given lib; tests change single fields with struct update syntax.
+#[cfg(test)]
+pub(crate) fn test_cli(input: impl Into<PathBuf>, lib: &str) -> Cli {
+    Cli {
+        input: input.into(),
+        output: None,
+        challenge_only: false,
+        modules: "all".to_string(),
+        block_hidden: "".to_string(),
+        block_modules: "".to_string(),
+        lib: lib.to_string(),
+        verbose: false,
+        simulate: false,
+        del_comments: false,
+        keep_empty_lines: false,
+        strip_panic_messages: false,
+        deny_warnings: false,
+        line_ending: None,
+        version_header: false,
+        keep_visibility: false,
+        libs_only: false,
+        group_uses: false,
+        no_marker: false,
+        script_shebang: false,
+        purge_with: "check".to_string(),
+        module_order_file: None,
+        stdout: false,
+        stats: false,
+        size_limit: 100_000,
+        minify: false,
+        keep_doc_comments: None,
+        strict_platform: false,
+        deterministic_temp: false,
+        supported_crates: "".to_string(),
+        purge_rounds: None,
+        bin: None,
+        purge_report: None,
+        print_config: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_config_to_toml() {
+        let options = Cli::from_iter([
+            "csf_cg",
+            "../csf_cg_binary_test/src/main.rs",
+            "--lib",
+            "csf_cg_lib_test",
+            "--line-ending",
+            "crlf",
+            "--purge-rounds",
+            "2",
+            "--print-config",
+        ]);
+        let config = options
+            .to_toml(&[])
+            .unwrap()
+            .parse::<toml::Table>()
+            .unwrap();
+        // values set by cli
+        assert_eq!(
+            config["input"].as_str(),
+            Some("../csf_cg_binary_test/src/main.rs")
+        );
+        assert_eq!(config["lib"].as_str(), Some("csf_cg_lib_test"));
+        assert_eq!(config["line_ending"].as_str(), Some("crlf"));
+        assert_eq!(config["purge_rounds"].as_integer(), Some(2));
+        // default values
+        assert_eq!(config["modules"].as_str(), Some("all"));
+        assert_eq!(config["block_hidden"].as_str(), Some(""));
+        assert_eq!(config["simulate"].as_bool(), Some(false));
+        assert_eq!(config["strict_platform"].as_bool(), Some(false));
+        assert_eq!(config["deterministic_temp"].as_bool(), Some(false));
+        assert!(!config.contains_key("output"));
+
+        // supported crates of cli merged with crates of CodinGame and csf_cg_config.toml
+        let options = Cli::from_iter([
+            "csf_cg",
+            "./test/supported_crates/src/main.rs",
+            "--supported-crates",
+            "serde",
+            "--print-config",
+        ]);
+        let config = crate::CGData::new(options)
+            .effective_config()
+            .unwrap()
+            .parse::<toml::Table>()
+            .unwrap();
+        let supported_crates = config["supported_crates"].as_array().unwrap();
+        for supported_crate in ["bitflags", "fixedbitset", "rand", "serde"] {
+            assert!(supported_crates.contains(&supported_crate.into()));
+        }
+    }
+}
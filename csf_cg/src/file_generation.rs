@@ -1,9 +1,14 @@
 use super::*;
-use std::collections::BTreeMap;
+use proc_macro2::{LineColumn, Span, TokenStream, TokenTree};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::fs;
 use std::path::Path;
-use syn::{visit::Visit, File, ItemMod, ItemUse, UseTree};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, visit::Visit, Attribute, Expr, ExprLit, Field, File,
+    ImplItem, Item, ItemExternCrate, ItemMacro, ItemMod, ItemUse, Lit, Meta, Token, TraitItem,
+    UseTree, Variant,
+};
 use toml::Value;
 
 use crate::configuration::*;
@@ -12,6 +17,7 @@ use crate::configuration::*;
 struct SrcVisitor {
     uses: Vec<ItemUse>,
     mods: Vec<ItemMod>,
+    extern_crates: Vec<ItemExternCrate>,
 }
 
 impl<'ast> Visit<'ast> for SrcVisitor {
@@ -21,6 +27,427 @@ impl<'ast> Visit<'ast> for SrcVisitor {
     fn visit_item_mod(&mut self, i: &'ast ItemMod) {
         self.mods.push(i.clone());
     }
+    fn visit_item_extern_crate(&mut self, i: &'ast ItemExternCrate) {
+        self.extern_crates.push(i.clone());
+    }
+}
+
+// item macros of std, which do not require a macro_rules! definition
+const BUILTIN_ITEM_MACROS: [&str; 4] = ["thread_local", "include", "compile_error", "global_asm"];
+
+#[derive(Default)]
+struct ItemMacroVisitor {
+    definitions: Vec<String>,
+    // name, first path segment of path with more than one segment and line
+    invocations: Vec<(String, Option<String>, usize)>,
+    // crates imported with #[macro_use] extern crate
+    macro_use_crates: Vec<String>,
+    // first path segment and imported name of use statements
+    imports: Vec<(String, String)>,
+}
+
+impl ItemMacroVisitor {
+    fn collect_imports(&mut self, root: &str, tree: &UseTree) {
+        match tree {
+            UseTree::Path(path) => self.collect_imports(root, &path.tree),
+            UseTree::Name(name) => self.imports.push((root.into(), name.ident.to_string())),
+            UseTree::Rename(rename) => self.imports.push((root.into(), rename.rename.to_string())),
+            UseTree::Group(group) => group
+                .items
+                .iter()
+                .for_each(|tree| self.collect_imports(root, tree)),
+            UseTree::Glob(_) => (),
+        }
+    }
+    // checks if macro is provided by one of supported crates
+    fn is_supported(&self, name: &str, root: Option<&String>, supported_crates: &[String]) -> bool {
+        match root {
+            // e.g. bitflags::bitflags! { ... }
+            Some(root) => supported_crates.contains(root),
+            // e.g. use bitflags::bitflags; or #[macro_use] extern crate bitflags;
+            None => {
+                self.imports
+                    .iter()
+                    .any(|(root, imported)| imported == name && supported_crates.contains(root))
+                    || self
+                        .macro_use_crates
+                        .iter()
+                        .any(|c| supported_crates.contains(c))
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ItemMacroVisitor {
+    fn visit_item_macro(&mut self, i: &'ast ItemMacro) {
+        let name = i.mac.path.segments.last().unwrap().ident.to_string();
+        match i.ident {
+            // macro_rules! name { ... }
+            Some(ref ident) => self.definitions.push(ident.to_string()),
+            None => self.invocations.push((
+                name,
+                (i.mac.path.segments.len() > 1).then(|| i.mac.path.segments[0].ident.to_string()),
+                i.mac.path.segments[0].ident.span().start().line,
+            )),
+        }
+        syn::visit::visit_item_macro(self, i);
+    }
+    fn visit_item_extern_crate(&mut self, i: &'ast ItemExternCrate) {
+        if i.attrs.iter().any(|attr| attr.path().is_ident("macro_use")) {
+            self.macro_use_crates.push(i.ident.to_string());
+        }
+    }
+    fn visit_item_use(&mut self, i: &'ast ItemUse) {
+        if let UseTree::Path(ref path) = i.tree {
+            self.collect_imports(&path.ident.to_string(), &path.tree);
+        }
+    }
+}
+
+// returns error, if output contains an item macro invocation, which is neither defined
+// with macro_rules! in output nor a builtin macro nor a macro of a supported crate.
+// Since all other dependencies except my_lib are not available on CodinGame, such a
+// macro is most likely a procedural macro.
+fn check_item_macros(output: &str, supported_crates: &[String]) -> BoxResult<()> {
+    // output, which cannot be parsed, is reported by cargo check later on
+    let Ok(syntax) = syn::parse_file(output) else {
+        return Ok(());
+    };
+    let mut visitor = ItemMacroVisitor::default();
+    visitor.visit_file(&syntax);
+    for (name, root, line) in visitor.invocations.iter() {
+        if BUILTIN_ITEM_MACROS.contains(&name.as_str())
+            || visitor.definitions.contains(name)
+            || visitor.is_supported(name, root.as_ref(), supported_crates)
+        {
+            continue;
+        }
+        // file of macro is given by last file marker before line of macro
+        let file = output
+            .lines()
+            .take(*line)
+            .filter_map(|l| l.trim_start().strip_prefix("//⏬"))
+            .last()
+            .unwrap_or_default()
+            .to_string();
+        return Err(Box::new(CGError::UnsupportedProcMacro(
+            name.to_owned(),
+            file,
+            *line,
+        )));
+    }
+    Ok(())
+}
+
+// evaluates cfg predicate, if it consists only of feature predicates combined with all, any or not
+fn eval_cfg_features(meta: &Meta, features: &BTreeSet<String>) -> Option<bool> {
+    match meta {
+        Meta::NameValue(name_value) if name_value.path.is_ident("feature") => {
+            match &name_value.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(feature),
+                    ..
+                }) => Some(features.contains(&feature.value())),
+                _ => None,
+            }
+        }
+        Meta::List(list) => {
+            let predicates = list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()?
+                .iter()
+                .map(|predicate| eval_cfg_features(predicate, features))
+                .collect::<Option<Vec<bool>>>()?;
+            if list.path.is_ident("all") {
+                Some(predicates.iter().all(|p| *p))
+            } else if list.path.is_ident("any") {
+                Some(predicates.iter().any(|p| *p))
+            } else if list.path.is_ident("not") && predicates.len() == 1 {
+                Some(!predicates[0])
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+// Struct to collect spans of code, which is removed because of cfg feature attributes
+struct CfgFeatureVisitor<'a> {
+    features: &'a BTreeSet<String>,
+    removals: Vec<(LineColumn, LineColumn)>,
+}
+
+impl CfgFeatureVisitor<'_> {
+    // removes span of node, if any cfg feature attribute is disabled; otherwise removes enabled cfg feature attributes
+    fn check_attributes(&mut self, attrs: &[Attribute], span: Span) -> bool {
+        let mut enabled_attributes = Vec::new();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("cfg")) {
+            let Ok(predicate) = attr.parse_args::<Meta>() else {
+                continue;
+            };
+            match eval_cfg_features(&predicate, self.features) {
+                Some(true) => enabled_attributes.push((attr.span().start(), attr.span().end())),
+                Some(false) => {
+                    self.removals.push((span.start(), span.end()));
+                    return false;
+                }
+                None => (),
+            }
+        }
+        self.removals.extend(enabled_attributes);
+        true
+    }
+}
+
+impl<'ast> Visit<'ast> for CfgFeatureVisitor<'_> {
+    fn visit_item(&mut self, i: &'ast Item) {
+        let attrs = match i {
+            Item::Const(item) => &item.attrs,
+            Item::Enum(item) => &item.attrs,
+            Item::ExternCrate(item) => &item.attrs,
+            Item::Fn(item) => &item.attrs,
+            Item::ForeignMod(item) => &item.attrs,
+            Item::Impl(item) => &item.attrs,
+            Item::Macro(item) => &item.attrs,
+            Item::Mod(item) => &item.attrs,
+            Item::Static(item) => &item.attrs,
+            Item::Struct(item) => &item.attrs,
+            Item::Trait(item) => &item.attrs,
+            Item::TraitAlias(item) => &item.attrs,
+            Item::Type(item) => &item.attrs,
+            Item::Union(item) => &item.attrs,
+            Item::Use(item) => &item.attrs,
+            _ => return syn::visit::visit_item(self, i),
+        };
+        if self.check_attributes(attrs, i.span()) {
+            syn::visit::visit_item(self, i);
+        }
+    }
+    fn visit_impl_item(&mut self, i: &'ast ImplItem) {
+        let attrs = match i {
+            ImplItem::Const(item) => &item.attrs,
+            ImplItem::Fn(item) => &item.attrs,
+            ImplItem::Type(item) => &item.attrs,
+            ImplItem::Macro(item) => &item.attrs,
+            _ => return syn::visit::visit_impl_item(self, i),
+        };
+        if self.check_attributes(attrs, i.span()) {
+            syn::visit::visit_impl_item(self, i);
+        }
+    }
+    fn visit_trait_item(&mut self, i: &'ast TraitItem) {
+        let attrs = match i {
+            TraitItem::Const(item) => &item.attrs,
+            TraitItem::Fn(item) => &item.attrs,
+            TraitItem::Type(item) => &item.attrs,
+            TraitItem::Macro(item) => &item.attrs,
+            _ => return syn::visit::visit_trait_item(self, i),
+        };
+        if self.check_attributes(attrs, i.span()) {
+            syn::visit::visit_trait_item(self, i);
+        }
+    }
+    fn visit_field(&mut self, i: &'ast Field) {
+        if self.check_attributes(&i.attrs, i.span()) {
+            syn::visit::visit_field(self, i);
+        }
+    }
+    fn visit_variant(&mut self, i: &'ast Variant) {
+        if self.check_attributes(&i.attrs, i.span()) {
+            syn::visit::visit_variant(self, i);
+        }
+    }
+}
+
+// removes items, impl items, trait items, fields and variants of code, which are gated by
+// cfg feature attributes of disabled features. Attributes of enabled features are removed,
+// since features of lib are not available in fused file.
+fn filter_cfg_features(code: &str, features: &BTreeSet<String>) -> String {
+    // code, which cannot be parsed, is reported by cargo check later on
+    let Ok(syntax) = syn::parse_file(code) else {
+        return code.to_string();
+    };
+    let mut visitor = CfgFeatureVisitor {
+        features,
+        removals: Vec::new(),
+    };
+    visitor.visit_file(&syntax);
+    remove_spans(code, &visitor.removals)
+}
+
+// removes code of spans. If nothing else remains in line of span, the complete line is removed.
+// returns function converting line and char column of span position to byte index of code
+fn byte_index_of(code: &str) -> impl Fn(LineColumn) -> usize + '_ {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(code.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+    move |position: LineColumn| {
+        let line_start = line_starts[position.line - 1];
+        code[line_start..]
+            .char_indices()
+            .nth(position.column)
+            .map(|(index, _)| line_start + index)
+            .unwrap_or(code.len())
+    }
+}
+
+fn remove_spans(code: &str, spans: &[(LineColumn, LineColumn)]) -> String {
+    let byte_index = byte_index_of(code);
+    let mut removals: Vec<(usize, usize)> = spans
+        .iter()
+        .map(|(start, end)| {
+            let (mut start, mut end) = (byte_index(*start), byte_index(*end));
+            // remove trailing comma of fields and variants
+            let tail = &code[end..];
+            if tail.trim_start().starts_with(',') {
+                end += tail.find(',').unwrap() + 1;
+            }
+            // remove complete line, if nothing else remains in line
+            let line_start = code[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let line_end = code[end..]
+                .find('\n')
+                .map(|i| end + i + 1)
+                .unwrap_or(code.len());
+            if code[line_start..start].trim().is_empty() && code[end..line_end].trim().is_empty() {
+                (start, end) = (line_start, line_end);
+            }
+            (start, end)
+        })
+        .collect();
+    removals.sort();
+    let mut filtered = String::with_capacity(code.len());
+    let mut index = 0;
+    for (start, end) in removals {
+        // removals inside of already removed code are skipped
+        if start >= index {
+            filtered.push_str(&code[index..start]);
+            index = end;
+        }
+    }
+    filtered.push_str(&code[index..]);
+    filtered
+}
+
+// collects spans of first segments of paths, which start with given alias of a crate. Paths
+// in token streams of macros are included; literals and comments contain no paths.
+struct CrateAliasVisitor<'a> {
+    alias: &'a str,
+    spans: Vec<(LineColumn, LineColumn)>,
+}
+
+impl CrateAliasVisitor<'_> {
+    fn check_ident(&mut self, ident: &proc_macro2::Ident) {
+        if ident == self.alias {
+            self.spans.push((ident.span().start(), ident.span().end()));
+        }
+    }
+    fn check_use_tree(&mut self, tree: &UseTree) {
+        match tree {
+            UseTree::Path(path) => self.check_ident(&path.ident),
+            UseTree::Name(name) => self.check_ident(&name.ident),
+            UseTree::Rename(rename) => self.check_ident(&rename.ident),
+            UseTree::Group(group) => group.items.iter().for_each(|t| self.check_use_tree(t)),
+            UseTree::Glob(_) => (),
+        }
+    }
+    fn check_tokens(&mut self, tokens: TokenStream) {
+        let is_colon = |token: Option<&TokenTree>| matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == ':');
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        for (index, token) in tokens.iter().enumerate() {
+            match token {
+                TokenTree::Group(group) => self.check_tokens(group.stream()),
+                TokenTree::Ident(ident)
+                    if !is_colon(index.checked_sub(1).and_then(|i| tokens.get(i)))
+                        && is_colon(tokens.get(index + 1))
+                        && is_colon(tokens.get(index + 2)) =>
+                {
+                    self.check_ident(ident)
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for CrateAliasVisitor<'_> {
+    fn visit_path(&mut self, i: &'ast syn::Path) {
+        if i.leading_colon.is_none() && i.segments.len() > 1 {
+            self.check_ident(&i.segments[0].ident);
+        }
+        syn::visit::visit_path(self, i);
+    }
+    fn visit_item_use(&mut self, i: &'ast ItemUse) {
+        if i.leading_colon.is_none() {
+            self.check_use_tree(&i.tree);
+        }
+    }
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        self.check_tokens(i.tokens.clone());
+        syn::visit::visit_macro(self, i);
+    }
+}
+
+// removes extern crate items of my_lib and of supported crates, which are not required since
+// edition 2018. Extern crates of supported crates with #[macro_use] are kept, since they import
+// macros for use without path. Since my_lib is fused into output, paths starting with alias of
+// my_lib are renamed to my_lib, which are handled like any other path of my_lib.
+fn rewrite_extern_crates(code: &str, lib: &str, supported_crates: &[String]) -> String {
+    // code, which cannot be parsed, is reported by cargo check later on
+    let Ok(syntax) = syn::parse_file(code) else {
+        return code.to_string();
+    };
+    let mut removals = Vec::new();
+    let mut lib_aliases = Vec::new();
+    for item in syntax.items.iter() {
+        let Item::ExternCrate(extern_crate) = item else {
+            continue;
+        };
+        let alias = extern_crate
+            .rename
+            .as_ref()
+            .map(|(_, alias)| alias.to_string());
+        let macro_use = extern_crate
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("macro_use"));
+        if extern_crate.ident == lib {
+            lib_aliases.extend(alias);
+        } else if !(alias.is_none()
+            && !macro_use
+            && supported_crates.contains(&extern_crate.ident.to_string()))
+        {
+            continue;
+        }
+        removals.push((item.span().start(), item.span().end()));
+    }
+    let code = remove_spans(code, &removals);
+    if lib_aliases.is_empty() {
+        return code;
+    }
+    let Ok(syntax) = syn::parse_file(&code) else {
+        return code;
+    };
+    let mut renames = Vec::new();
+    for alias in lib_aliases.iter() {
+        let mut crate_alias_visitor = CrateAliasVisitor {
+            alias,
+            spans: Vec::new(),
+        };
+        crate_alias_visitor.visit_file(&syntax);
+        renames.extend(crate_alias_visitor.spans);
+    }
+    renames.sort();
+    let byte_index = byte_index_of(&code);
+    let mut renamed = String::with_capacity(code.len());
+    let mut index = 0;
+    for (start, end) in renames {
+        renamed.push_str(&code[index..byte_index(start)]);
+        renamed.push_str(lib);
+        index = byte_index(end);
+    }
+    renamed.push_str(&code[index..]);
+    renamed
 }
 
 impl SrcVisitor {
@@ -33,11 +460,20 @@ impl SrcVisitor {
         let mut visitor = SrcVisitor {
             uses: Vec::new(),
             mods: Vec::new(),
+            extern_crates: Vec::new(),
         };
         // Visit the syntax tree and collect all use statements
         visitor.visit_file(&syntax);
         Ok(visitor)
     }
+    // names of my_lib given by extern crate my_lib as alias;
+    fn lib_aliases(&self, lib: &str) -> Vec<String> {
+        self.extern_crates
+            .iter()
+            .filter(|e| e.ident == lib)
+            .filter_map(|e| e.rename.as_ref().map(|(_, alias)| alias.to_string()))
+            .collect()
+    }
 }
 
 #[derive(Clone)]
@@ -59,6 +495,8 @@ impl CGData {
         // set directory, which contains the modules, if there are some
         let mod_dir = match src_path.file_name().unwrap().to_str().unwrap() {
             "main.rs" | "lib.rs" | "mod.rs" => src_path.parent().unwrap().to_path_buf(),
+            // modules of binary files in src/bin are located next to binary file
+            _ if current_module == "bin_crate" => src_path.parent().unwrap().to_path_buf(),
             _ => {
                 let mut mod_dir = src_path.clone();
                 mod_dir.set_extension("");
@@ -75,9 +513,36 @@ impl CGData {
         // create visitor from source code
         let visitor = SrcVisitor::new(&src_path)?;
 
-        // parse mod entries, which are empty
-        for item_mod in visitor.mods.iter().filter(|m| m.content.is_none()) {
+        self.parse_mod_items(&visitor.mods, &mod_dir, current_module, modules, verbose)
+    }
+    fn parse_mod_items(
+        &self,
+        item_mods: &[ItemMod],
+        mod_dir: &Path,
+        current_module: String,
+        modules: &mut BTreeMap<String, PathBuf>,
+        verbose: bool,
+    ) -> BoxResult<()> {
+        for item_mod in item_mods.iter() {
             let mut module = item_mod.ident.to_string();
+            if let Some((_, ref items)) = item_mod.content {
+                // modules declared inside of inline module are located in sub dir with name of inline module
+                let inline_mods: Vec<ItemMod> = items
+                    .iter()
+                    .filter_map(|i| match i {
+                        Item::Mod(m) => Some(m.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                self.parse_mod_items(
+                    &inline_mods,
+                    &mod_dir.join(&module),
+                    current_module.clone() + "::" + &module,
+                    modules,
+                    verbose,
+                )?;
+                continue;
+            }
             let mut path = mod_dir.join(module.clone() + ".rs");
             if !path.is_file() {
                 path.set_extension("");
@@ -90,7 +555,8 @@ impl CGData {
                 continue;
             }
             if self.options.verbose && verbose {
-                println!(
+                progress!(
+                    self.options,
                     "found module \"{}\", adding {} to module list...",
                     module,
                     path.display()
@@ -106,7 +572,7 @@ impl CGData {
             || self.options.modules.split(';').any(|m| m == "lib"))
         {
             if self.options.verbose {
-                println!("\"lib\" (or \"all\") not in given list of modules -> skipping collecting path of local modules of crate...");
+                progress!(self.options, "\"lib\" (or \"all\") not in given list of modules -> skipping collecting path of local modules of crate...");
             }
             return Ok(());
         }
@@ -114,7 +580,11 @@ impl CGData {
         let mut local_modules = BTreeMap::new();
         // parse modules of bin_crate
         if self.options.verbose {
-            println!("collecting \"{}\" modules of bin_crate...", self.crate_name);
+            progress!(
+                self.options,
+                "collecting \"{}\" modules of bin_crate...",
+                self.crate_name
+            );
         }
         self.parse_mod_from_src_file(
             self.tmp_input_file.clone(),
@@ -134,7 +604,8 @@ impl CGData {
             let lib_rs = self.crate_dir.join("src").join("lib.rs");
             // add lib.rs to local_modules
             if self.options.verbose {
-                println!(
+                progress!(
+                    self.options,
                     "found module \"{}\", adding {} to module list...",
                     self.crate_name,
                     lib_rs.display(),
@@ -143,7 +614,11 @@ impl CGData {
             local_modules.insert(self.crate_name.clone(), lib_rs.clone());
             // parse modules of lib_crate
             if self.options.verbose {
-                println!("collecting \"{}\" modules of lib_crate...", self.crate_name);
+                progress!(
+                    self.options,
+                    "collecting \"{}\" modules of lib_crate...",
+                    self.crate_name
+                );
             }
             self.parse_mod_from_src_file(lib_rs, "lib_crate".into(), &mut local_modules, true)?;
         }
@@ -151,6 +626,20 @@ impl CGData {
         self.local_modules = local_modules;
         Ok(())
     }
+    fn is_blocked_module(&self, module: &str, found_in: &str) -> bool {
+        if !self.options.block_modules.split(';').any(|b| b == module) {
+            return false;
+        }
+        if self.options.verbose {
+            progress!(
+                self.options,
+                "blocked module {} (found in {})...",
+                module,
+                found_in
+            );
+        }
+        true
+    }
     fn parse_use_item(
         &mut self,
         use_tree: &UseTree,
@@ -164,12 +653,20 @@ impl CGData {
                 match parse_mode {
                     ParseUseMode::InputCrate => {
                         if mod_name.is_empty() {
-                            if module != self.options.lib {
+                            if module != self.options.lib && !self.lib_aliases.contains(&module) {
                                 // use statement does not refer to my_lib
                                 return;
                             }
-                            self.parse_use_item(&use_path.tree, module, parse_mode, lib_modules);
+                            self.parse_use_item(
+                                &use_path.tree,
+                                self.options.lib.clone(),
+                                parse_mode,
+                                lib_modules,
+                            );
                         } else {
+                            if self.is_blocked_module(&module, &mod_name) {
+                                return;
+                            }
                             let extend_mod_name = mod_name + "::" + &module;
                             self.parse_use_item(
                                 &use_path.tree,
@@ -216,12 +713,17 @@ impl CGData {
                                     // ignore my_lib dependencies
                                     return;
                                 }
+                                if self.is_blocked_module(&module, src_module) {
+                                    return;
+                                }
                                 if self.options.block_hidden.split(';').any(|b| b == module) {
                                     // block hidden module
                                     if self.options.verbose {
-                                        println!(
+                                        progress!(
+                                            self.options,
                                             "blocked hidden module {} (found in {})...",
-                                            module, src_module
+                                            module,
+                                            src_module
                                         );
                                     }
                                     return;
@@ -252,12 +754,14 @@ impl CGData {
                 let path = lib_modules.get(&mod_name).unwrap();
                 if self.options.verbose {
                     match parse_mode {
-                        ParseUseMode::InputCrate => println!(
+                        ParseUseMode::InputCrate => progress!(
+                            self.options,
                             "found module \"{}\", adding {} to module list...",
                             mod_name,
                             path.display()
                         ),
-                        ParseUseMode::HiddenModulesInMyLib(_) => println!(
+                        ParseUseMode::HiddenModulesInMyLib(_) => progress!(
+                            self.options,
                             "found hidden module \"{}\", adding {} to module list...",
                             mod_name,
                             path.display()
@@ -276,16 +780,8 @@ impl CGData {
         if let Some(ref my_lib) = self.my_lib {
             let my_lib_toml = fs::read_to_string(my_lib.parent().unwrap().join("Cargo.toml"))?
                 .parse::<Value>()?;
-            for (dep_name, _) in my_lib_toml
-                .as_table()
-                .unwrap()
-                .get("dependencies")
-                .unwrap()
-                .as_table()
-                .unwrap()
-                .iter()
-            {
-                dependencies.push(dep_name.clone());
+            if let Some(Value::Table(lib_dependencies)) = my_lib_toml.get("dependencies") {
+                dependencies.extend(lib_dependencies.keys().cloned());
             }
         }
 
@@ -295,7 +791,8 @@ impl CGData {
         // get lib modules if modules if not challenge_only and my_lib is specified
         if self.options.challenge_only {
             if self.options.verbose {
-                println!(
+                progress!(
+                    self.options,
                     "challenge_only -> skipping collecting path of all specified modules of lib..."
                 );
             }
@@ -306,7 +803,7 @@ impl CGData {
             Some(ref my_lib) => my_lib.clone(),
             None => {
                 if self.options.verbose {
-                    println!("lib \"{}\" not specified in toml -> skipping collecting path of all specified modules of lib...", self.options.lib);
+                    progress!(self.options, "lib \"{}\" not specified in toml -> skipping collecting path of all specified modules of lib...", self.options.lib);
                 }
                 return Ok(());
             }
@@ -317,7 +814,11 @@ impl CGData {
         lib_modules.insert(self.options.lib.clone(), my_lib.join("lib.rs"));
         // parse modules of lib
         if self.options.verbose {
-            println!("collecting all modules of \"{}\"...", self.options.lib);
+            progress!(
+                self.options,
+                "collecting all modules of \"{}\"...",
+                self.options.lib
+            );
         }
         self.parse_mod_from_src_file(
             my_lib.join("lib.rs"),
@@ -332,6 +833,7 @@ impl CGData {
         // parse use statements in main.rs
         // create visitor from source code
         let visitor = SrcVisitor::new(&self.tmp_input_file)?;
+        self.lib_aliases = visitor.lib_aliases(&self.options.lib);
         for use_item in visitor.uses.iter() {
             self.parse_use_item(
                 &use_item.tree,
@@ -347,6 +849,8 @@ impl CGData {
         for local_modules_path in local_modules.iter() {
             // create visitor from source code
             let visitor = SrcVisitor::new(local_modules_path)?;
+            self.lib_aliases
+                .extend(visitor.lib_aliases(&self.options.lib));
             for use_item in visitor.uses.iter() {
                 self.parse_use_item(
                     &use_item.tree,
@@ -388,7 +892,9 @@ impl CGData {
         if let Some(byte_index) = data.find("#[cfg(test)]") {
             data.truncate(byte_index);
         }
-        data = data.replace("pub ", "");
+        if !self.options.keep_visibility {
+            data = data.replace("pub ", "");
+        }
         if !output.is_empty() {
             output.push_str(self.line_end_chars.as_str());
         }
@@ -408,9 +914,17 @@ impl CGData {
     }
     fn load_lib(&self, path: &Path, output: &mut String) -> BoxResult<()> {
         if self.options.verbose {
-            println!("loading lib module {:?}...", path.file_name().unwrap());
+            progress!(
+                self.options,
+                "loading lib module {:?}...",
+                path.file_name().unwrap()
+            );
         }
+        let lib_start = output.len();
         self.load(path, output)?;
+        let lib_code = filter_cfg_features(&output[lib_start..], &self.lib_features);
+        output.truncate(lib_start);
+        output.push_str(&lib_code);
         // filter usage of modules of crate, since all modules will be copied into one single file
         /* *output = output
         .lines()
@@ -423,9 +937,21 @@ impl CGData {
     }
     fn load_challenge(&self, path: &Path, output: &mut String) -> BoxResult<()> {
         if self.options.verbose {
-            println!("loading challenge code {:?}...", path.file_name().unwrap());
+            progress!(
+                self.options,
+                "loading challenge code {:?}...",
+                path.file_name().unwrap()
+            );
         }
+        let challenge_start = output.len();
         self.load(path, output)?;
+        let challenge_code = rewrite_extern_crates(
+            &output[challenge_start..],
+            &self.options.lib,
+            &self.supported_crates()?,
+        );
+        output.truncate(challenge_start);
+        output.push_str(&challenge_code);
         // remove lines including use of lib, local crate or modules of local crate
         /*let lib_pattern = "use ".to_string() + self.options.lib.as_str() + "::";
         let local_crate_pattern = "use ".to_string() + self.crate_name.as_str() + "::";
@@ -443,6 +969,12 @@ impl CGData {
     fn insert(&self, input: &mut str, output: &mut String) -> BoxResult<()> {
         let start_marker = input.lines().next().unwrap().to_string() + self.line_end_chars.as_str();
         let end_marker = input.lines().last().unwrap().to_string();
+        // without markers input would replace random parts of output
+        for marker in [start_marker.trim_end(), end_marker.as_str()] {
+            if !output.contains(marker) {
+                return Err(Box::new(CGError::MissingMarker(marker.into())));
+            }
+        }
         let pre_start_marker = output
             .split(start_marker.as_str())
             .next()
@@ -457,7 +989,11 @@ impl CGData {
             let mut input = String::new();
             self.load_lib(path, &mut input)?;
             if self.options.verbose {
-                println!("inserting {:?} into output...", path.file_name().unwrap());
+                progress!(
+                    self.options,
+                    "inserting {:?} into output...",
+                    path.file_name().unwrap()
+                );
             }
             self.insert(&mut input, output)?;
         }
@@ -470,25 +1006,92 @@ impl CGData {
             let mut input = String::new();
             self.load_challenge(file_input, &mut input)?;
             if self.options.verbose {
-                println!("inserting {} into output...", file_input.display());
+                progress!(
+                    self.options,
+                    "inserting {} into output...",
+                    file_input.display()
+                );
             }
             self.insert(&mut input, output)?;
         }
         Ok(())
     }
-    pub fn create_output(&mut self) -> BoxResult<()> {
+    // returns path of lib and local modules and if module is lib module.
+    // Modules listed in module order file come first, unlisted modules follow in default order.
+    fn ordered_modules(&self) -> BoxResult<Vec<(PathBuf, bool)>> {
+        let mut modules: Vec<(&String, &PathBuf, bool)> = self
+            .lib_modules
+            .iter()
+            .map(|(name, path)| (name, path, true))
+            .chain(
+                self.local_modules
+                    .iter()
+                    .map(|(name, path)| (name, path, false)),
+            )
+            .collect();
+        if let Some(ref module_order_file) = self.options.module_order_file {
+            if self.options.verbose {
+                progress!(
+                    self.options,
+                    "ordering modules by {}",
+                    module_order_file.display()
+                );
+            }
+            let order = fs::read_to_string(module_order_file)?;
+            let order: Vec<&str> = order
+                .lines()
+                .map(|l| l.trim())
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .collect();
+            // stable sort keeps default order of unlisted modules
+            modules.sort_by_key(|(name, _, _)| {
+                order.iter().position(|o| o == name).unwrap_or(order.len())
+            });
+        }
+        Ok(modules
+            .into_iter()
+            .map(|(_, path, is_lib)| (path.to_owned(), is_lib))
+            .collect())
+    }
+    // fuses given files and main file into one string without parsing any crate.
+    // Since there is no crate to check with cargo, unused code is not filtered.
+    // Items of given files are filtered by given active features; supported crates
+    // are extended by csf_cg_config.toml in config_dir, if present.
+    pub fn fuse_files(
+        &mut self,
+        main_file: &Path,
+        files: &[PathBuf],
+        features: &BTreeSet<String>,
+        config_dir: &Path,
+    ) -> BoxResult<String> {
+        if self.line_end_chars.is_empty() {
+            self.line_end_chars = self.detect_line_end_chars(main_file)?;
+        }
+        self.lib_features = features.clone();
+        self.crate_dir = config_dir.to_path_buf();
+        let mut output = String::new();
+        for path in files.iter() {
+            self.load_lib(path, &mut output)?;
+        }
+        self.load_challenge(main_file, &mut output)?;
+        Ok(output)
+    }
+    /// Fuses challenge and lib modules and returns fused code without saving it.
+    /// Requires prepare_cg_data(); unused code is not yet filtered.
+    pub fn fusion_to_string(&mut self) -> BoxResult<String> {
         self.get_local_modules()?;
         self.get_lib_modules()?;
         let mut output = String::new();
         if self.options.challenge_only {
             if self.options.verbose {
-                println!("insert option challenge_only is active");
+                progress!(self.options, "insert option challenge_only is active");
             }
             self.load_output(&mut output)?;
             self.insert_challenge(&mut output)?;
         } else if self.options.modules.as_str() != "all" {
             if self.options.verbose {
-                println!(
+                progress!(
+                    self.options,
                     "insert option specific module(s) is active: {}",
                     self.options.modules
                 );
@@ -497,19 +1100,34 @@ impl CGData {
             self.insert_challenge(&mut output)?;
             self.insert_lib(&mut output)?;
         } else {
-            for path in self.lib_modules.values() {
-                self.load_lib(path.as_path(), &mut output)?;
+            let libs_only = self.options.libs_only;
+            if libs_only && self.options.verbose {
+                progress!(self.options, "insert option libs_only is active");
+            }
+            for (path, is_lib) in self.ordered_modules()? {
+                if is_lib {
+                    self.load_lib(path.as_path(), &mut output)?;
+                } else if !libs_only {
+                    self.load_challenge(path.as_path(), &mut output)?;
+                }
             }
-            for path in self.local_modules.values() {
-                self.load_challenge(path.as_path(), &mut output)?;
+            if !libs_only {
+                self.load_challenge(self.tmp_input_file.as_path(), &mut output)?;
             }
-            self.load_challenge(self.tmp_input_file.as_path(), &mut output)?;
         }
+        check_item_macros(&output, &self.supported_crates()?)?;
+        Ok(output)
+    }
+    pub fn create_output(&mut self) -> BoxResult<()> {
+        let output = self.fusion_to_string()?;
         if self.options.simulate {
-            println!("End of simulation");
+            self.fusion_size.set(output.chars().count());
+            progress!(self.options, "{}", self.simulation_summary()?);
+            progress!(self.options, "End of simulation");
         } else {
             if self.options.verbose {
-                println!(
+                progress!(
+                    self.options,
                     "saving output into tmp file {:#?}",
                     self.tmp_output_file.as_path()
                 );
@@ -535,11 +1153,35 @@ mod tests {
             challenge_only: false,
             modules: "all".to_string(),
             block_hidden: "".to_string(),
+            block_modules: "".to_string(),
             lib: "csf_cg_lib_test".to_string(),
             verbose: false,
             simulate: true,
             del_comments: false,
             keep_empty_lines: false,
+            strip_panic_messages: false,
+            deny_warnings: false,
+            line_ending: None,
+            version_header: false,
+            keep_visibility: false,
+            libs_only: false,
+            group_uses: false,
+            no_marker: false,
+            script_shebang: false,
+            purge_with: "check".to_string(),
+            module_order_file: None,
+            stdout: false,
+            stats: false,
+            size_limit: 100_000,
+            minify: false,
+            keep_doc_comments: None,
+            strict_platform: false,
+            deterministic_temp: false,
+            supported_crates: "".to_string(),
+            purge_rounds: None,
+            bin: None,
+            purge_report: None,
+            print_config: false,
         };
         // simulate output
         let mut data = CGData::new(options);
@@ -564,11 +1206,35 @@ mod tests {
             challenge_only: false,
             modules: "all".to_string(),
             block_hidden: "my_compass;my_array".to_string(),
+            block_modules: "".to_string(),
             lib: "csf_cg_lib_test".to_string(),
             verbose: false,
             simulate: true,
             del_comments: false,
             keep_empty_lines: false,
+            strip_panic_messages: false,
+            deny_warnings: false,
+            line_ending: None,
+            version_header: false,
+            keep_visibility: false,
+            libs_only: false,
+            group_uses: false,
+            no_marker: false,
+            script_shebang: false,
+            purge_with: "check".to_string(),
+            module_order_file: None,
+            stdout: false,
+            stats: false,
+            size_limit: 100_000,
+            minify: false,
+            keep_doc_comments: None,
+            strict_platform: false,
+            deterministic_temp: false,
+            supported_crates: "".to_string(),
+            purge_rounds: None,
+            bin: None,
+            purge_report: None,
+            print_config: false,
         };
         // simulate output
         let mut data = CGData::new(options);
@@ -584,20 +1250,506 @@ mod tests {
         assert!(!data.tmp_dir.is_dir());
     }
 
+    #[test]
+    fn test_file_modules_in_inline_module() {
+        let input = PathBuf::from(r"./test/inline_modules/main.rs");
+        let options = Cli {
+            simulate: true,
+            ..test_cli(input.clone(), "csf_cg_lib_test")
+        };
+        let data = CGData::new(options);
+        let mut modules = BTreeMap::new();
+        data.parse_mod_from_src_file(input, "bin_crate".into(), &mut modules, true)
+            .unwrap();
+
+        // inline modules are no files and therefore not added to modules
+        assert_eq!(modules.len(), 2);
+        assert_eq!(
+            modules.get("bin_crate::outer::inner"),
+            Some(&PathBuf::from(r"./test/inline_modules/outer/inner.rs"))
+        );
+        assert_eq!(
+            modules.get("bin_crate::outer::inner::deep"),
+            Some(&PathBuf::from(r"./test/inline_modules/outer/inner/deep.rs"))
+        );
+    }
+
+    #[test]
+    fn test_parse_use_item_with_self_in_group() {
+        let options = Cli {
+            simulate: true,
+            ..test_cli(r"../csf_cg_binary_test/src/main.rs", "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        let my_lib = data.my_lib.clone().unwrap();
+        let mut lib_modules = BTreeMap::new();
+        lib_modules.insert("csf_cg_lib_test".to_string(), my_lib.join("lib.rs"));
+        data.parse_mod_from_src_file(
+            my_lib.join("lib.rs"),
+            "csf_cg_lib_test".into(),
+            &mut lib_modules,
+            false,
+        )
+        .unwrap();
+
+        // "self" in group refers to module of group path
+        let use_item: ItemUse =
+            syn::parse_str("use csf_cg_lib_test::my_map_two_dim::{self, my_map_point::MapPoint};")
+                .unwrap();
+        data.parse_use_item(
+            &use_item.tree,
+            "".into(),
+            &ParseUseMode::InputCrate,
+            &lib_modules,
+        );
+        assert_eq!(data.lib_modules.len(), 2);
+        assert_eq!(
+            data.lib_modules.get("csf_cg_lib_test::my_map_two_dim"),
+            lib_modules.get("csf_cg_lib_test::my_map_two_dim")
+        );
+        assert_eq!(
+            data.lib_modules
+                .get("csf_cg_lib_test::my_map_two_dim::my_map_point"),
+            lib_modules.get("csf_cg_lib_test::my_map_two_dim::my_map_point")
+        );
+        // "self" in group of lib root refers to lib.rs
+        let use_item: ItemUse =
+            syn::parse_str("use csf_cg_lib_test::{self, my_array::MyArray};").unwrap();
+        data.parse_use_item(
+            &use_item.tree,
+            "".into(),
+            &ParseUseMode::InputCrate,
+            &lib_modules,
+        );
+        assert_eq!(data.lib_modules.len(), 4);
+        assert!(data.lib_modules.contains_key("csf_cg_lib_test"));
+        assert!(data.lib_modules.contains_key("csf_cg_lib_test::my_array"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_parse_use_item_with_rename_in_nested_group() {
+        let options = Cli {
+            simulate: true,
+            ..test_cli(r"../csf_cg_binary_test/src/main.rs", "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        let my_lib = data.my_lib.clone().unwrap();
+        let mut lib_modules = BTreeMap::new();
+        lib_modules.insert("csf_cg_lib_test".to_string(), my_lib.join("lib.rs"));
+        data.parse_mod_from_src_file(
+            my_lib.join("lib.rs"),
+            "csf_cg_lib_test".into(),
+            &mut lib_modules,
+            false,
+        )
+        .unwrap();
+
+        // renames in nested groups refer to items of leaf modules
+        let use_item: ItemUse = syn::parse_str(
+            "use csf_cg_lib_test::{my_map_two_dim::{my_map_point::{my_compass::{Compass as Dir}, MapPoint as Point}, MyMap2D as Grid}, my_array::{MyArray as Array}};",
+        )
+        .unwrap();
+        data.parse_use_item(
+            &use_item.tree,
+            "".into(),
+            &ParseUseMode::InputCrate,
+            &lib_modules,
+        );
+        assert_eq!(data.lib_modules.len(), 4);
+        for module in [
+            "csf_cg_lib_test::my_map_two_dim",
+            "csf_cg_lib_test::my_map_two_dim::my_map_point",
+            "csf_cg_lib_test::my_map_two_dim::my_map_point::my_compass",
+            "csf_cg_lib_test::my_array",
+        ] {
+            assert_eq!(data.lib_modules.get(module), lib_modules.get(module));
+        }
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_filter_cfg_features() {
+        let code = r#"//⏬map.rs
+#[cfg(feature = "grid")]
+#[derive(Clone)]
+struct Grid {
+    cells: Vec<u8>,
+}
+#[derive(Default)]
+struct Cell {
+    value: u8,
+    #[cfg(any(feature = "grid", feature = "hex"))]
+    neighbors: Vec<usize>,
+}
+impl Cell {
+    #[cfg(all(feature = "grid", not(feature = "hex")))]
+    fn is_square(&self) -> bool {
+        true
+    }
+    #[cfg(target_os = "linux")]
+    fn platform(&self) {}
+}
+//⏫map.rs"#;
+        let features = BTreeSet::from(["grid".to_string()]);
+        assert_eq!(
+            filter_cfg_features(code, &features),
+            r#"//⏬map.rs
+#[derive(Clone)]
+struct Grid {
+    cells: Vec<u8>,
+}
+#[derive(Default)]
+struct Cell {
+    value: u8,
+    neighbors: Vec<usize>,
+}
+impl Cell {
+    fn is_square(&self) -> bool {
+        true
+    }
+    #[cfg(target_os = "linux")]
+    fn platform(&self) {}
+}
+//⏫map.rs"#
+        );
+        let features = BTreeSet::new();
+        assert_eq!(
+            filter_cfg_features(code, &features),
+            r#"//⏬map.rs
+#[derive(Default)]
+struct Cell {
+    value: u8,
+}
+impl Cell {
+    #[cfg(target_os = "linux")]
+    fn platform(&self) {}
+}
+//⏫map.rs"#
+        );
+    }
+
+    #[test]
+    fn test_fusion_to_string() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let options = Cli {
+            simulate: true,
+            ..test_cli(input, "shared")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        let fusion = data.fusion_to_string().unwrap();
+        assert!(!data.tmp_dir.exists());
+        let syntax = syn::parse_file(&fusion).unwrap();
+        assert!(syntax
+            .items
+            .iter()
+            .any(|item| matches!(item, Item::Fn(item_fn) if item_fn.sig.ident == "main")));
+    }
+
+    #[test]
+    fn test_rewrite_extern_crates() {
+        let code = r#"//⏬main.rs
+extern crate rand;
+extern crate regex as re;
+#[macro_use]
+extern crate itertools;
+extern crate shared as arr;
+use arr::{add, my_arr::MyArray};
+// arr::add is fused
+fn main() {
+    let my_arr = MyArray::new();
+    let value = my_arr::LEN + arr::my_arr::LEN;
+    let pairs = iproduct!(0..2, 0..2).count();
+    println!("arr::add: {}", arr::add(value, pairs));
+}
+//⏫main.rs"#;
+        let supported_crates = ["itertools", "rand", "regex"].map(String::from);
+        assert_eq!(
+            rewrite_extern_crates(code, "shared", &supported_crates),
+            r#"//⏬main.rs
+extern crate regex as re;
+#[macro_use]
+extern crate itertools;
+use shared::{add, my_arr::MyArray};
+// arr::add is fused
+fn main() {
+    let my_arr = MyArray::new();
+    let value = my_arr::LEN + shared::my_arr::LEN;
+    let pairs = iproduct!(0..2, 0..2).count();
+    println!("arr::add: {}", shared::add(value, pairs));
+}
+//⏫main.rs"#
+        );
+    }
+
+    #[test]
+    fn test_block_modules() {
+        let input = PathBuf::from(r"./test/block_modules/challenge/src/main.rs");
+        let options = Cli {
+            block_modules: "debug".to_string(),
+            simulate: true,
+            ..test_cli(input, "toolbox")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        let fusion = data.fusion_to_string().unwrap();
+        assert!(data.lib_modules.contains_key("toolbox::geometry"));
+        assert!(!data.lib_modules.contains_key("toolbox::debug"));
+        assert!(fusion.contains("fn manhattan("));
+        assert!(!fusion.contains("fn dump("));
+    }
+
+    #[test]
+    fn test_check_item_macros() {
+        let output = r#"//⏬my_macros.rs
+macro_rules! define_point {
+    () => {
+        struct Point;
+    };
+}
+//⏫my_macros.rs
+//⏬main.rs
+define_point!();
+thread_local! {
+    static COUNTER: u32 = 0;
+}
+fn main() {}
+//⏫main.rs"#;
+        check_item_macros(output, &[]).unwrap();
+
+        let output = output.replace("thread_local! {", "define_grid! {");
+        let err = check_item_macros(&output, &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported procedural macro \"define_grid!\" in main.rs (line 10 of output); only macros defined with macro_rules! can be fused"
+        );
+
+        // declarative macros of supported crates are available on CodinGame
+        let supported_crates = ["bitflags".to_string()];
+        let with_path = "bitflags::bitflags! {\n    struct Flags: u8 {}\n}\nfn main() {}";
+        check_item_macros(with_path, &supported_crates).unwrap();
+        assert!(check_item_macros(with_path, &[]).is_err());
+        let imported = format!(
+            "use bitflags::bitflags;\n{}",
+            with_path.replace("bitflags::bitflags!", "bitflags!")
+        );
+        check_item_macros(&imported, &supported_crates).unwrap();
+        let macro_use = format!(
+            "#[macro_use]\nextern crate bitflags;\n{}",
+            with_path.replace("bitflags::bitflags!", "bitflags!")
+        );
+        check_item_macros(&macro_use, &supported_crates).unwrap();
+        assert!(check_item_macros(&macro_use, &[]).is_err());
+    }
+
+    #[test]
+    fn test_insert_requires_markers() {
+        let options = Cli {
+            challenge_only: true,
+            simulate: true,
+            ..test_cli(r"../csf_cg_binary_test/src/main.rs", "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.line_end_chars = "\n".into();
+        let mut input = "//⏬main.rs\nfn main() {}\n//⏫main.rs".to_string();
+
+        // markers are replaced including code between them
+        let mut output =
+            "//⏬a.rs\nfn a() {}\n//⏫a.rs\n//⏬main.rs\nfn main() { a(); }\n//⏫main.rs\n"
+                .to_string();
+        data.insert(&mut input, &mut output).unwrap();
+        assert!(output.starts_with(
+            "//⏬a.rs\nfn a() {}\n//⏫a.rs\n//⏬main.rs\nfn main() {}\n//⏫main.rs\n"
+        ));
+        assert!(!output.contains("a(); }"));
+
+        // output without markers is not touched
+        let mut output = "fn a() {}\nfn main() { a(); }\n".to_string();
+        let err = data.insert(&mut input, &mut output).unwrap_err();
+        assert!(err.to_string().contains("//⏬main.rs"));
+        assert_eq!(output, "fn a() {}\nfn main() { a(); }\n");
+    }
+
+    #[test]
+    fn test_load_keeps_repr_attributes() {
+        let options = Cli {
+            simulate: true,
+            ..test_cli(r"../csf_cg_binary_test/src/main.rs", "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.line_end_chars = "\n".into();
+        let mut output = String::new();
+        data.load(Path::new(r"./test/attributes/cell.rs"), &mut output)
+            .unwrap();
+        assert!(output.contains("#[repr(u8)]\n#[derive(Copy, Clone, PartialEq)]\nenum Cell {"));
+        assert!(output.contains("#[repr(C)]\nstruct Position {"));
+    }
+
+    #[test]
+    fn test_load_keeps_tests_module_without_cfg_test() {
+        let options = Cli {
+            simulate: true,
+            ..test_cli(r"../csf_cg_binary_test/src/main.rs", "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.line_end_chars = "\n".into();
+        let mut output = String::new();
+        data.load(Path::new(r"./test/tests_module/fixtures.rs"), &mut output)
+            .unwrap();
+        // module named tests is kept, only code following #[cfg(test)] is removed
+        assert!(output.contains("mod tests {\n    struct Sample {\n        value: u8,"));
+        assert!(!output.contains("cfg(test)"));
+        assert!(!output.contains("unit_tests"));
+    }
+
+    #[test]
+    fn test_keep_visibility() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            keep_visibility: true,
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        // create output with original visibility
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.create_output().unwrap();
+        let mut file_content = String::new();
+        data.load_output(&mut file_content).unwrap();
+        assert!(file_content.contains("pub struct MyMap2D<"));
+        assert!(file_content.contains("pub fn apply_action(&mut self, action: Action)"));
+
+        // create output with minimized visibility
+        data.options.keep_visibility = false;
+        data.create_output().unwrap();
+        data.load_output(&mut file_content).unwrap();
+        assert!(file_content.contains("\nstruct MyMap2D<"));
+        assert!(!file_content.contains("pub struct MyMap2D<"));
+        assert!(file_content.contains("    fn apply_action(&mut self, action: Action)"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_libs_only() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            libs_only: true,
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        // create output without challenge code
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.create_output().unwrap();
+        let mut file_content = String::new();
+        data.load_output(&mut file_content).unwrap();
+        assert!(file_content.contains("struct MyMap2D<"));
+        assert!(!file_content.contains("fn main()"));
+
+        // lib code is not filtered
+        data.filter_unused_code().unwrap();
+        data.load_output(&mut file_content).unwrap();
+        assert!(file_content.contains("struct MyMap2D<"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_module_order_file() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            module_order_file: Some(PathBuf::from(r"./test/module_order_file/order.txt")),
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.create_output().unwrap();
+        let mut file_content = String::new();
+        data.load_output(&mut file_content).unwrap();
+        let position = |marker: &str| file_content.find(marker).unwrap();
+
+        // listed modules in order of file
+        assert!(position("//⏬my_array.rs") < position("//⏬my_map_point.rs"));
+        assert!(position("//⏬my_map_point.rs") < position("//⏬action.rs"));
+        // unlisted modules follow
+        assert!(position("//⏬action.rs") < position("//⏬my_map_two_dim.rs"));
+        assert!(position("//⏬my_map_two_dim.rs") < position("//⏬my_compass.rs"));
+        // challenge code is always last
+        assert!(file_content.trim_end().ends_with("//⏫main.rs"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_fuse_files() {
+        let options = test_cli(PathBuf::new(), "my_lib");
+        let mut data = CGData::new(options);
+        // only feature "triple" is active
+        let output = data
+            .fuse_files(
+                Path::new(r"./test/explicit_files/main.rs"),
+                &[PathBuf::from(r"./test/explicit_files/helper.rs")],
+                &BTreeSet::from(["triple".to_string()]),
+                Path::new(r"./test/explicit_files"),
+            )
+            .unwrap();
+        let expected = "//⏬helper.rs\nfn double(value: u32) -> u32 {\n    value * 2\n}\nfn triple(value: u32) -> u32 {\n    value * 3\n}\n//⏫helper.rs\n//⏬main.rs\nfn main() {\n    println!(\"{} {}\", double(21), triple(14));\n}\n//⏫main.rs";
+        assert_eq!(output, expected);
+        assert!(syn::parse_file(&output).is_ok());
+    }
+
     #[test]
     fn test_creation_tmp_file_output() {
         let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
         let options = Cli {
-            input: input,
+            input,
             output: None,
             challenge_only: false,
             modules: "all".to_string(),
             block_hidden: "my_compass;my_array".to_string(),
+            block_modules: "".to_string(),
             lib: "csf_cg_lib_test".to_string(),
             verbose: false,
             simulate: false,
             del_comments: false,
             keep_empty_lines: false,
+            strip_panic_messages: false,
+            deny_warnings: false,
+            line_ending: None,
+            version_header: false,
+            keep_visibility: false,
+            libs_only: false,
+            group_uses: false,
+            no_marker: false,
+            script_shebang: false,
+            purge_with: "check".to_string(),
+            module_order_file: None,
+            stdout: false,
+            stats: false,
+            size_limit: 100_000,
+            minify: false,
+            keep_doc_comments: None,
+            strict_platform: false,
+            deterministic_temp: false,
+            supported_crates: "".to_string(),
+            purge_rounds: None,
+            bin: None,
+            purge_report: None,
+            print_config: false,
         };
         // create output
         let mut data = CGData::new(options);
@@ -1,12 +1,28 @@
+// progress messages are written to stderr, if fused output is written to stdout
+macro_rules! progress {
+    ($options:expr, $($arg:tt)*) => {
+        if $options.output_to_stdout() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
 pub mod configuration;
 pub mod file_generation;
 pub mod post_generation;
 
-use std::collections::BTreeMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant};
 use std::{io, io::Write};
+use syn::fold::Fold;
+use syn::visit::Visit;
 use toml::Value;
 use uuid::Uuid;
 
@@ -39,6 +55,354 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> io::Result<()> {
     Ok(())
 }
 
+/// External crates, which are available on CodinGame.
+pub const CODINGAME_SUPPORTED_CRATES: [&str; 6] =
+    ["chrono", "itertools", "libc", "rand", "regex", "time"];
+
+/// Config file in crate dir, which may extend supported crates with
+/// `[platform] supported_crates = [...]`.
+pub const CONFIG_FILE: &str = "csf_cg_config.toml";
+
+/// Marker file in tmp dir, which identifies tmp dir as created by csf_cg.
+const TMP_DIR_MARKER: &str = ".csf_cg_tmp_dir";
+
+/// 64 bit FNV-1a hash, which does not change between releases of rust.
+fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Searches parent directories of crate_dir for Cargo.toml of a workspace.
+fn find_workspace_root(crate_dir: &Path) -> BoxResult<Option<(PathBuf, toml::Table)>> {
+    let crate_dir = fs::canonicalize(crate_dir)?;
+    for dir in crate_dir.ancestors().skip(1) {
+        let toml_path = dir.join("Cargo.toml");
+        if !toml_path.is_file() {
+            continue;
+        }
+        let toml = fs::read_to_string(toml_path)?.parse::<Value>()?;
+        if let Some(Value::Table(workspace)) = toml.get("workspace") {
+            return Ok(Some((dir.to_path_buf(), workspace.clone())));
+        }
+    }
+    Ok(None)
+}
+
+/// Replaces all keys and dependencies of manifest, which are inherited from workspace,
+/// with their values in workspace and marks manifest as root of its own workspace.
+/// This is required, because cargo check runs on a copy of the crate, which is not member of workspace.
+fn resolve_workspace_inheritance(
+    manifest: &mut Value,
+    workspace_dir: &Path,
+    workspace: &toml::Table,
+) {
+    let is_inherited =
+        |value: &Value| value.get("workspace").and_then(|w| w.as_bool()) == Some(true);
+    let manifest = manifest.as_table_mut().unwrap();
+    if let (Some(Value::Table(package)), Some(Value::Table(workspace_package))) =
+        (manifest.get_mut("package"), workspace.get("package"))
+    {
+        for (key, value) in package.iter_mut() {
+            if is_inherited(value) {
+                if let Some(workspace_value) = workspace_package.get(key) {
+                    *value = workspace_value.clone();
+                }
+            }
+        }
+    }
+    if let Some(Value::Table(workspace_dependencies)) = workspace.get("dependencies") {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(Value::Table(dependencies)) = manifest.get_mut(section) else {
+                continue;
+            };
+            for (name, dependency) in dependencies.iter_mut() {
+                if !is_inherited(dependency) {
+                    continue;
+                }
+                let Some(workspace_dependency) = workspace_dependencies.get(name) else {
+                    continue;
+                };
+                let mut resolved = match workspace_dependency {
+                    Value::String(version) => {
+                        let mut resolved = toml::Table::new();
+                        resolved.insert("version".into(), version.as_str().into());
+                        resolved
+                    }
+                    Value::Table(table) => table.clone(),
+                    _ => continue,
+                };
+                // paths in workspace are relative to workspace dir
+                if let Some(path) = resolved.get("path").and_then(|p| p.as_str()) {
+                    let path = workspace_dir.join(path).display().to_string();
+                    resolved.insert("path".into(), path.into());
+                }
+                // keep member specific keys like features or optional
+                for (key, value) in dependency.as_table().unwrap().iter() {
+                    if key != "workspace" {
+                        resolved.insert(key.to_owned(), value.clone());
+                    }
+                }
+                *dependency = Value::Table(resolved);
+            }
+        }
+    }
+    manifest
+        .entry("workspace")
+        .or_insert_with(|| Value::Table(toml::Table::new()));
+}
+
+/// Collects features of lib, which are enabled by dependency of crate on lib.
+/// Features of lib, which are enabled by other features, are resolved recursively.
+fn active_lib_features(dependency: &Value, lib_manifest: &Value) -> BTreeSet<String> {
+    let lib_features = lib_manifest.get("features").and_then(|f| f.as_table());
+    let mut pending: Vec<String> = dependency
+        .get("features")
+        .and_then(|f| f.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|f| f.as_str().map(|f| f.to_string()))
+        .collect();
+    if dependency
+        .get("default-features")
+        .and_then(|d| d.as_bool())
+        .unwrap_or(true)
+        && lib_features.is_some_and(|f| f.contains_key("default"))
+    {
+        pending.push("default".into());
+    }
+    let mut features = BTreeSet::new();
+    while let Some(feature) = pending.pop() {
+        // features of dependencies of lib are not relevant for code of lib
+        if feature.contains('/') || !features.insert(feature.clone()) {
+            continue;
+        }
+        if let Some(Value::Array(enabled)) = lib_features.and_then(|f| f.get(&feature)) {
+            pending.extend(
+                enabled
+                    .iter()
+                    .filter_map(|f| f.as_str())
+                    .map(|f| f.trim_start_matches("dep:").to_string()),
+            );
+        }
+    }
+    features
+}
+
+/// Lists dependencies of manifest, which are compiled with given active features of package.
+/// Optional dependencies are only compiled, if an active feature enables them.
+fn active_dependencies(manifest: &Value, features: &BTreeSet<String>) -> Vec<String> {
+    let Some(Value::Table(dependencies)) = manifest.get("dependencies") else {
+        return Vec::new();
+    };
+    // "dep:name" is already resolved to "name" in features; "name/feature" enables "name", too
+    let enabled_by_features: Vec<&str> = manifest
+        .get("features")
+        .and_then(|f| f.as_table())
+        .into_iter()
+        .flat_map(|f| f.iter())
+        .filter(|(feature, _)| features.contains(*feature))
+        .filter_map(|(_, enabled)| enabled.as_array())
+        .flatten()
+        .filter_map(|f| f.as_str()?.split_once('/'))
+        .map(|(dependency, _)| dependency)
+        .filter(|dependency| !dependency.ends_with('?'))
+        .collect();
+    dependencies
+        .iter()
+        .filter(|(name, dependency)| {
+            !dependency
+                .get("optional")
+                .and_then(|o| o.as_bool())
+                .unwrap_or_default()
+                || features.contains(*name)
+                || enabled_by_features.contains(&name.as_str())
+        })
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+/// Crate dir of input, which must be an existing main.rs or binary file in src/bin.
+fn crate_dir_of_input(input: &Path) -> BoxResult<PathBuf> {
+    if !input.is_file()
+        || !(input.file_name().unwrap() == "main.rs"
+            || input.parent().unwrap().ends_with("src/bin"))
+    {
+        return Err(Box::new(CGError::MustProvideInPutFile));
+    }
+    let crate_dir = input.parent().unwrap();
+    match crate_dir.file_name().unwrap().to_str().unwrap() {
+        "bin" => Ok(crate_dir.parent().unwrap().parent().unwrap().to_path_buf()),
+        "src" => Ok(crate_dir.parent().unwrap().to_path_buf()),
+        _ => Err(Box::new(CGError::PackageStructureError(input.into()))),
+    }
+}
+
+/// Checks if package of manifest uses edition 2015, which is the default of cargo if
+/// no edition is given. Edition inherited from workspace is never 2015.
+fn is_edition_2015(manifest: &Value) -> bool {
+    match manifest.get("package").and_then(|p| p.get("edition")) {
+        Some(Value::String(edition)) => edition == "2015",
+        Some(_) => false,
+        None => true,
+    }
+}
+
+/// Searches source file of binary target in package given by package dir or its Cargo.toml.
+/// Binary targets are given by [[bin]] sections of Cargo.toml, src/main.rs with name of
+/// package and files in src/bin.
+fn find_binary_target(package: &Path, bin: &str) -> BoxResult<PathBuf> {
+    let package_dir = if package.file_name().is_some_and(|f| f == "Cargo.toml") {
+        package.parent().unwrap()
+    } else {
+        package
+    };
+    let toml_path = package_dir.join("Cargo.toml");
+    if !toml_path.is_file() {
+        return Err(Box::new(CGError::PackageStructureError(package.into())));
+    }
+    let toml = fs::read_to_string(toml_path)?.parse::<Value>()?;
+    let mut binary_targets: BTreeMap<String, PathBuf> = BTreeMap::new();
+    let main_rs = package_dir.join("src").join("main.rs");
+    if main_rs.is_file() {
+        let package_name = toml["package"]["name"].as_str().unwrap_or_default();
+        binary_targets.insert(package_name.into(), main_rs);
+    }
+    let bin_dir = package_dir.join("src").join("bin");
+    if bin_dir.is_dir() {
+        for entry in fs::read_dir(bin_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "rs") {
+                let name = path.file_stem().unwrap().to_string_lossy().to_string();
+                binary_targets.insert(name, path);
+            }
+        }
+    }
+    if let Some(Value::Array(bin_targets)) = toml.get("bin") {
+        for bin_target in bin_targets.iter() {
+            if let (Some(name), Some(path)) = (
+                bin_target.get("name").and_then(|n| n.as_str()),
+                bin_target.get("path").and_then(|p| p.as_str()),
+            ) {
+                // file of explicit target is no target of its own
+                let path = package_dir.join(path);
+                binary_targets.retain(|_, p| *p != path);
+                binary_targets.insert(name.into(), path);
+            }
+        }
+    }
+    binary_targets.remove(bin).ok_or_else(|| {
+        Box::new(CGError::BinaryNotFound(
+            bin.into(),
+            binary_targets.into_keys().collect(),
+        ))
+        .into()
+    })
+}
+
+/// Runs one processing stage and measures its duration.
+fn timed<T>(
+    stage_durations: &mut Vec<(&'static str, Duration)>,
+    stage: &'static str,
+    run_stage: impl FnOnce() -> BoxResult<T>,
+) -> BoxResult<T> {
+    let start = Instant::now();
+    let result = run_stage()?;
+    stage_durations.push((stage, start.elapsed()));
+    Ok(result)
+}
+
+/// Checks if given tool of rust toolchain can be executed.
+fn check_toolchain(tool: &str) -> BoxResult<()> {
+    match Command::new(tool).arg("--version").output() {
+        Ok(output) if output.status.success() => Ok(()),
+        _ => Err(Box::new(CGError::MissingToolchain(tool.into()))),
+    }
+}
+
+/// Numbers of fused output for reporting.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FusionStats {
+    pub local_modules: usize,
+    pub lib_modules: usize,
+    pub supported_dependencies: usize,
+    pub unsupported_dependencies: usize,
+    /// all items of output including items inside of modules, impl blocks are counted as one item each
+    pub items: usize,
+    pub impl_blocks: usize,
+    pub characters: usize,
+}
+
+#[derive(Default)]
+struct ItemCounter {
+    items: usize,
+    impl_blocks: usize,
+}
+
+impl<'ast> Visit<'ast> for ItemCounter {
+    fn visit_item(&mut self, i: &'ast syn::Item) {
+        self.items += 1;
+        if let syn::Item::Impl(_) = i {
+            self.impl_blocks += 1;
+        }
+        syn::visit::visit_item(self, i);
+    }
+}
+
+/// Collects lines of items, impl items and trait items with given ident.
+struct ItemNameFinder<'a> {
+    name: &'a str,
+    lines: Vec<usize>,
+}
+
+impl ItemNameFinder<'_> {
+    fn check_ident(&mut self, ident: Option<&syn::Ident>) {
+        if let Some(ident) = ident.filter(|ident| *ident == self.name) {
+            self.lines.push(ident.span().start().line);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ItemNameFinder<'_> {
+    fn visit_item(&mut self, i: &'ast syn::Item) {
+        let ident = match i {
+            syn::Item::Const(item) => Some(&item.ident),
+            syn::Item::Enum(item) => Some(&item.ident),
+            syn::Item::Fn(item) => Some(&item.sig.ident),
+            syn::Item::Macro(item) => item.ident.as_ref(),
+            syn::Item::Mod(item) => Some(&item.ident),
+            syn::Item::Static(item) => Some(&item.ident),
+            syn::Item::Struct(item) => Some(&item.ident),
+            syn::Item::Trait(item) => Some(&item.ident),
+            syn::Item::Type(item) => Some(&item.ident),
+            syn::Item::Union(item) => Some(&item.ident),
+            _ => None,
+        };
+        self.check_ident(ident);
+        syn::visit::visit_item(self, i);
+    }
+    fn visit_impl_item(&mut self, i: &'ast syn::ImplItem) {
+        let ident = match i {
+            syn::ImplItem::Const(item) => Some(&item.ident),
+            syn::ImplItem::Fn(item) => Some(&item.sig.ident),
+            syn::ImplItem::Type(item) => Some(&item.ident),
+            _ => None,
+        };
+        self.check_ident(ident);
+        syn::visit::visit_impl_item(self, i);
+    }
+    fn visit_trait_item(&mut self, i: &'ast syn::TraitItem) {
+        let ident = match i {
+            syn::TraitItem::Const(item) => Some(&item.ident),
+            syn::TraitItem::Fn(item) => Some(&item.sig.ident),
+            syn::TraitItem::Type(item) => Some(&item.ident),
+            _ => None,
+        };
+        self.check_ident(ident);
+        syn::visit::visit_trait_item(self, i);
+    }
+}
+
 pub struct CGData {
     options: Cli,
     crate_dir: PathBuf,
@@ -46,11 +410,18 @@ pub struct CGData {
     local_modules: BTreeMap<String, PathBuf>,
     my_lib: Option<PathBuf>,
     lib_modules: BTreeMap<String, PathBuf>,
+    lib_features: BTreeSet<String>,
+    lib_aliases: Vec<String>,
     tmp_dir: PathBuf,
     tmp_input_file: PathBuf,
     tmp_output_file: PathBuf,
     output_file: PathBuf,
     line_end_chars: String,
+    post_transforms: RefCell<Vec<Box<dyn Fold>>>,
+    stage_durations: Vec<(&'static str, Duration)>,
+    fusion_size: Cell<usize>,
+    // cargo executable, which checks tmp crate
+    cargo: String,
 }
 
 impl CGData {
@@ -62,46 +433,82 @@ impl CGData {
             local_modules: BTreeMap::new(),
             my_lib: None,
             lib_modules: BTreeMap::new(),
+            lib_features: BTreeSet::new(),
+            lib_aliases: Vec::new(),
             tmp_dir: PathBuf::new(),
             tmp_input_file: PathBuf::new(),
             tmp_output_file: PathBuf::new(),
             output_file: PathBuf::new(),
             line_end_chars: "".to_string(),
+            post_transforms: RefCell::new(Vec::new()),
+            stage_durations: Vec::new(),
+            fusion_size: Cell::new(0),
+            cargo: "cargo".to_string(),
         };
+        // panic messages are stripped by a post transform
+        if result.options.strip_panic_messages {
+            result.add_post_transform(Box::new(post_generation::StripPanicMessages));
+        }
         if result.options.simulate {
-            println!("Start of simulation");
+            progress!(result.options, "Start of simulation");
             result.options.verbose = true;
         }
         if result.options.verbose {
-            println!("{}", result.options);
+            progress!(result.options, "{}", result.options);
         }
         result
     }
+    /// Registers a fold, which is applied to the fused file after filtering unused code.
+    /// If any fold is registered, the fused file is printed again with prettyplease, which
+    /// removes all comments except doc comments and all file markers from output.
+    pub fn add_post_transform(&mut self, transform: Box<dyn Fold>) {
+        self.post_transforms.get_mut().push(transform);
+    }
     pub fn prepare_cg_data(&mut self) -> BoxResult<()> {
+        // cargo is required to filter unused code
+        if !self.options.simulate {
+            if self.options.verbose {
+                progress!(self.options, "checking availability of cargo...");
+            }
+            check_toolchain(&self.cargo)?;
+            if self.options.purge_with == "clippy" {
+                check_toolchain("cargo-clippy")?;
+            }
+        }
         if self.options.verbose {
-            println!("reading path of lib from toml file...");
-        }
-        // only accept existing main.rs as input
-        if !self.options.input.is_file() || self.options.input.file_name().unwrap() != "main.rs" {
-            return Err(Box::new(CGError::MustProvideInPutFile));
-        }
-        let crate_dir = self.options.input.as_path().parent().unwrap();
-        self.crate_dir = match crate_dir.file_name().unwrap().to_str().unwrap() {
-            "bin" => crate_dir.parent().unwrap().parent().unwrap().to_path_buf(),
-            "src" => crate_dir.parent().unwrap().to_path_buf(),
-            _ => {
-                return Err(Box::new(CGError::PackageStructureError(
-                    self.options.input.clone(),
-                )))
+            progress!(self.options, "reading path of lib from toml file...");
+        }
+        if let Some(ref bin) = self.options.bin {
+            self.options.input = find_binary_target(&self.options.input, bin)?;
+            if self.options.verbose {
+                progress!(
+                    self.options,
+                    "input of binary target {}: {}",
+                    bin,
+                    self.options.input.display()
+                );
             }
-        };
+        }
+        self.crate_dir = crate_dir_of_input(&self.options.input)?;
         // get toml content
         let toml_path = self.crate_dir.join("Cargo.toml");
         if self.options.verbose {
-            println!("crate_dir: {}", self.crate_dir.display());
-            println!("toml_path: {}", toml_path.display());
+            progress!(self.options, "crate_dir: {}", self.crate_dir.display());
+            progress!(self.options, "toml_path: {}", toml_path.display());
+        }
+        let mut toml = fs::read_to_string(toml_path.clone())?.parse::<Value>()?;
+        // resolve keys inherited from workspace, if crate is member of a workspace
+        let workspace_root = find_workspace_root(&self.crate_dir)?;
+        if let Some((ref workspace_dir, ref workspace)) = workspace_root {
+            if self.options.verbose {
+                progress!(
+                    self.options,
+                    "crate is member of workspace {}",
+                    workspace_dir.display()
+                );
+            }
+            resolve_workspace_inheritance(&mut toml, workspace_dir, workspace);
         }
-        let toml = fs::read_to_string(toml_path.clone())?.parse::<Value>()?;
         // get package name
         let package = toml
             .as_table()
@@ -114,20 +521,22 @@ impl CGData {
             Some(crate_name) => {
                 self.crate_name = crate_name.to_string().trim().replace('\"', "");
                 if self.options.verbose {
-                    println!("crate name: {}", self.crate_name);
+                    progress!(self.options, "crate name: {}", self.crate_name);
                 }
             }
             None => panic!("could not find package name in {}", toml_path.display()),
         }
+        // fusion expects module semantics of edition 2018 or later
+        if is_edition_2015(&toml) {
+            return Err(Box::new(CGError::UnsupportedEdition(
+                self.crate_name.clone(),
+            )));
+        }
         // get lib path, if any is used
-        let dependencies = toml
-            .as_table()
-            .unwrap()
+        match toml
             .get("dependencies")
-            .unwrap()
-            .as_table()
-            .unwrap();
-        match dependencies.get(self.options.lib.as_str()) {
+            .and_then(|dependencies| dependencies.get(self.options.lib.as_str()))
+        {
             Some(my_lib) => {
                 let mut my_lib_path = self.crate_dir.clone();
                 for lib_path_element in Path::new(
@@ -145,61 +554,104 @@ impl CGData {
                     my_lib_path.push(lib_path_element);
                 }
                 if self.options.verbose {
-                    println!(
+                    progress!(
+                        self.options,
                         "path if lib {}: {}",
                         self.options.lib,
                         my_lib_path.display()
                     );
                 }
+                let lib_manifest = my_lib_path.parent().unwrap().join("Cargo.toml");
+                let lib_manifest = fs::read_to_string(lib_manifest)?.parse::<Value>()?;
+                if is_edition_2015(&lib_manifest) {
+                    return Err(Box::new(CGError::UnsupportedEdition(
+                        self.options.lib.clone(),
+                    )));
+                }
+                self.lib_features = active_lib_features(my_lib, &lib_manifest);
+                if self.options.verbose {
+                    progress!(
+                        self.options,
+                        "active features of lib {}: {:?}",
+                        self.options.lib,
+                        self.lib_features
+                    );
+                }
                 self.my_lib = Some(my_lib_path);
             }
             None => {
                 if self.options.verbose {
-                    println!("lib \"{}\" not specified in toml", self.options.lib);
+                    progress!(
+                        self.options,
+                        "lib \"{}\" not specified in toml",
+                        self.options.lib
+                    );
                 }
             }
         }
+        if self.options.strict_platform {
+            let unsupported_dependencies = self.list_unsupported_dependencies()?;
+            if !unsupported_dependencies.is_empty() {
+                return Err(Box::new(CGError::UnsupportedDependencies(
+                    unsupported_dependencies,
+                )));
+            }
+        }
         // prepare working directory
         // tmp dir must be on same path as crate dir, otherwise relative paths im Cargo.toml will not work
-        self.tmp_dir = self
-            .crate_dir
-            .parent()
-            .unwrap()
-            .join(String::from(Uuid::new_v4()));
-        if self.options.verbose {
-            println!(
-                "creating tmp working directory for cargo check: {}",
-                self.tmp_dir.display()
-            );
+        self.tmp_dir = self.crate_dir.parent().unwrap().join(self.tmp_name());
+        // remove remains of previous run, which was not cleaned up
+        if self.options.deterministic_temp && self.tmp_dir.is_dir() {
+            if !self.tmp_dir.join(TMP_DIR_MARKER).is_file() {
+                return Err(Box::new(CGError::ForeignTmpDir(self.tmp_dir.clone())));
+            }
+            fs::remove_dir_all(&self.tmp_dir)?;
         }
-        fs::create_dir_all(&self.tmp_dir)?;
-        fs::copy(
-            self.crate_dir.join("Cargo.toml"),
-            self.tmp_dir.join("Cargo.toml"),
-        )?;
-        let bin_dir = self.tmp_dir.join("src").join("bin");
-        fs::create_dir_all(&bin_dir)?;
-        copy_dir_recursive(&self.crate_dir.join("src"), &self.tmp_dir.join("src"))?;
+        // simulation works directly on crate dir without creating any file
+        let work_dir = if self.options.simulate {
+            self.crate_dir.clone()
+        } else {
+            if self.options.verbose {
+                progress!(
+                    self.options,
+                    "creating tmp working directory for cargo check: {}",
+                    self.tmp_dir.display()
+                );
+            }
+            fs::create_dir_all(&self.tmp_dir)?;
+            fs::write(self.tmp_dir.join(TMP_DIR_MARKER), "")?;
+            if workspace_root.is_some() {
+                fs::write(self.tmp_dir.join("Cargo.toml"), toml::to_string(&toml)?)?;
+            } else {
+                fs::copy(
+                    self.crate_dir.join("Cargo.toml"),
+                    self.tmp_dir.join("Cargo.toml"),
+                )?;
+            }
+            fs::create_dir_all(self.tmp_dir.join("src").join("bin"))?;
+            copy_dir_recursive(&self.crate_dir.join("src"), &self.tmp_dir.join("src"))?;
+            self.tmp_dir.clone()
+        };
+        let bin_dir = work_dir.join("src").join("bin");
         if self.options.output.is_none() {
             if self.options.challenge_only || self.options.modules.as_str() != "all" {
                 // these options require an already existing output file to insert changed code
                 return Err(Box::new(CGError::MustProvideOutPutFile));
             }
             if self.options.verbose {
-                println!("creating tmp bin file path for cargo check...");
+                progress!(
+                    self.options,
+                    "creating tmp bin file path for cargo check..."
+                );
             }
-            let tmp_file = String::from(Uuid::new_v4()) + ".rs";
+            let tmp_file = self.tmp_name() + ".rs";
             self.tmp_output_file = bin_dir.join(tmp_file);
         } else {
             self.output_file = self.options.output.as_ref().unwrap().clone();
             if self.crate_dir.join("src").join("bin") != self.output_file.parent().unwrap() {
                 return Err(Box::new(CGError::OutputFileError(self.output_file.clone())));
             }
-            self.tmp_output_file = self
-                .tmp_dir
-                .join("src")
-                .join("bin")
-                .join(self.output_file.file_name().unwrap());
+            self.tmp_output_file = bin_dir.join(self.output_file.file_name().unwrap());
         }
         // set new variable tmp_input
         self.tmp_input_file = if self
@@ -214,19 +666,28 @@ impl CGData {
             .unwrap()
             == "src"
         {
-            self.tmp_dir.join("src").join("main.rs")
-        } else {
-            self.tmp_dir.join("src").join("bin").join("main.rs")
-        };
-        // checking for line end chars (either \n or \r\n)
-        let input = fs::read_to_string(&self.tmp_input_file)?;
-        self.line_end_chars = if input.contains("\r\n") {
-            "\r\n".to_string()
+            work_dir.join("src").join("main.rs")
         } else {
-            "\n".to_string()
+            bin_dir.join(self.options.input.file_name().unwrap())
         };
+        self.line_end_chars = self.detect_line_end_chars(&self.tmp_input_file)?;
         Ok(())
     }
+    fn detect_line_end_chars(&self, input_file: &Path) -> BoxResult<String> {
+        // checking for line end chars (either \n or \r\n), if not set by options
+        Ok(match self.options.line_ending.as_deref() {
+            Some("crlf") => "\r\n".to_string(),
+            Some(_) => "\n".to_string(),
+            None => {
+                let input = fs::read_to_string(input_file)?;
+                if input.contains("\r\n") {
+                    "\r\n".to_string()
+                } else {
+                    "\n".to_string()
+                }
+            }
+        })
+    }
     fn load_output(&self, output: &mut String) -> BoxResult<()> {
         *output = fs::read_to_string(self.tmp_output_file.as_path())?;
         Ok(())
@@ -238,29 +699,207 @@ impl CGData {
         Ok(())
     }
     pub fn cleanup_cg_data(&self) -> BoxResult<String> {
-        let output = if self.options.simulate {
-            "".into()
-        } else if self.options.output.is_none() {
-            if self.options.verbose {
-                println!("create output from tmp file before clean up...");
+        let mut output = String::new();
+        if !self.options.simulate {
+            if self.options.output_to_stdout() {
+                if self.options.verbose {
+                    progress!(
+                        self.options,
+                        "create output from tmp file before clean up..."
+                    );
+                }
+                self.load_output(&mut output)?;
+            }
+            if self.options.output.is_some() {
+                if self.options.verbose {
+                    progress!(self.options, "saving output to output file...");
+                }
+                fs::copy(&self.tmp_output_file, &self.output_file)?;
             }
-            let mut output = String::new();
-            self.load_output(&mut output)?;
-            output
-        } else {
             if self.options.verbose {
-                println!("saving output to output file...");
+                progress!(self.options, "removing tmp dir...");
             }
-            fs::copy(&self.tmp_output_file, &self.output_file)?;
-            "".into()
+            // delete working tmp dir
+            fs::remove_dir_all(self.tmp_dir.as_path())?;
+        }
+        Ok(output)
+    }
+    // random UUID or, if deterministic_temp is set, hash of crate name and bin name
+    fn tmp_name(&self) -> String {
+        if !self.options.deterministic_temp {
+            return String::from(Uuid::new_v4());
+        }
+        let hash = fnv1a_hash(format!("{}\0{}", self.crate_name, self.bin_name()).as_bytes());
+        format!("csf_cg_{:016x}", hash)
+    }
+    // name of binary target of input: src/main.rs is named after crate, src/bin/*.rs after file
+    fn bin_name(&self) -> String {
+        if let Some(ref bin) = self.options.bin {
+            return bin.clone();
+        }
+        match self.options.input.file_stem().and_then(|s| s.to_str()) {
+            Some("main") | None => self.crate_name.clone(),
+            Some(file_stem) => file_stem.to_string(),
+        }
+    }
+    /// Effective configuration as TOML including supported crates of csf_cg_config.toml in crate dir.
+    pub fn effective_config(&mut self) -> BoxResult<String> {
+        let input = match self.options.bin {
+            Some(ref bin) => find_binary_target(&self.options.input, bin)?,
+            None => self.options.input.clone(),
         };
-        if self.options.verbose {
-            println!("removing tmp dir...");
+        self.crate_dir = crate_dir_of_input(&input)?;
+        self.options.to_toml(&self.supported_crates()?)
+    }
+    /// Supported external crates: crates of CodinGame, crates of config file and crates of cli option.
+    pub fn supported_crates(&self) -> BoxResult<Vec<String>> {
+        let mut supported_crates: Vec<String> = CODINGAME_SUPPORTED_CRATES
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let config_file = self.crate_dir.join(CONFIG_FILE);
+        if config_file.is_file() {
+            let config = fs::read_to_string(config_file)?.parse::<Value>()?;
+            if let Some(Value::Array(crates)) = config
+                .get("platform")
+                .and_then(|p| p.get("supported_crates"))
+            {
+                supported_crates.extend(crates.iter().filter_map(|c| c.as_str()).map(String::from));
+            }
+        }
+        supported_crates.extend(
+            self.options
+                .supported_crates
+                .split(';')
+                .filter(|c| !c.is_empty())
+                .map(String::from),
+        );
+        supported_crates.sort();
+        supported_crates.dedup();
+        Ok(supported_crates)
+    }
+    /// Dependencies of crate and lib, which are not supported, each with the package
+    /// pulling it in. Lib itself is not listed, since it is fused into output.
+    // collects compiled dependencies of crate and lib except lib with name of package, which requires them
+    fn list_external_dependencies(&self) -> BoxResult<Vec<(String, String)>> {
+        // crate is compiled with its default features, lib with features enabled by crate
+        let crate_toml = fs::read_to_string(self.crate_dir.join("Cargo.toml"))?.parse::<Value>()?;
+        let crate_features = active_lib_features(&Value::Table(toml::Table::new()), &crate_toml);
+        let mut packages = vec![(crate_toml, crate_features)];
+        if let Some(ref my_lib) = self.my_lib {
+            let lib_toml = fs::read_to_string(my_lib.parent().unwrap().join("Cargo.toml"))?
+                .parse::<Value>()?;
+            packages.push((lib_toml, self.lib_features.clone()));
+        }
+        let mut external_dependencies = Vec::new();
+        for (toml, features) in packages.iter() {
+            let package_name = toml["package"]["name"].as_str().unwrap_or_default();
+            for name in active_dependencies(toml, features) {
+                if name != self.options.lib {
+                    external_dependencies.push((name, package_name.to_owned()));
+                }
+            }
+        }
+        Ok(external_dependencies)
+    }
+    pub fn list_unsupported_dependencies(&self) -> BoxResult<Vec<String>> {
+        let supported_crates = self.supported_crates()?;
+        Ok(self
+            .list_external_dependencies()?
+            .into_iter()
+            .filter(|(name, _)| !supported_crates.contains(name))
+            .map(|(name, package_name)| format!("{} (required by {})", name, package_name))
+            .collect())
+    }
+    /// Numbers of current output for reporting. Must be called after create_output()
+    /// or filter_unused_code() and before cleanup_cg_data().
+    pub fn statistics(&self) -> BoxResult<FusionStats> {
+        let supported_crates = self.supported_crates()?;
+        let (supported_dependencies, unsupported_dependencies): (Vec<_>, Vec<_>) = self
+            .list_external_dependencies()?
+            .into_iter()
+            .partition(|(name, _)| supported_crates.contains(name));
+        let mut output = String::new();
+        self.load_output(&mut output)?;
+        let mut item_counter = ItemCounter::default();
+        item_counter.visit_file(&syn::parse_file(&output)?);
+        Ok(FusionStats {
+            local_modules: self.local_modules.len(),
+            lib_modules: self.lib_modules.len(),
+            supported_dependencies: supported_dependencies.len(),
+            unsupported_dependencies: unsupported_dependencies.len(),
+            items: item_counter.items,
+            impl_blocks: item_counter.impl_blocks,
+            characters: output.chars().count(),
+        })
+    }
+    /// Lines of output with items, impl items or trait items named by given ident.
+    pub fn find_items_by_name(&self, name: &str) -> BoxResult<Vec<usize>> {
+        let mut output = String::new();
+        self.load_output(&mut output)?;
+        let mut item_name_finder = ItemNameFinder {
+            name,
+            lines: Vec::new(),
+        };
+        item_name_finder.visit_file(&syn::parse_file(&output)?);
+        Ok(item_name_finder.lines)
+    }
+    /// Number of characters of output after filtering unused code.
+    pub fn fusion_size(&self) -> usize {
+        self.fusion_size.get()
+    }
+    /// Summary of simulated output: number of fused modules, external dependencies and characters.
+    pub fn simulation_summary(&self) -> BoxResult<String> {
+        let external_dependencies = self.list_external_dependencies()?.len();
+        Ok(format!(
+            "summary: {} local module(s), {} lib module(s), {} external dependencies, {} characters before filtering unused code",
+            self.local_modules.len(),
+            self.lib_modules.len(),
+            external_dependencies,
+            self.fusion_size()
+        ))
+    }
+    /// Runs all processing stages and returns output, if output is directed to stdout.
+    pub fn run(&mut self) -> BoxResult<String> {
+        let mut stage_durations = Vec::new();
+        timed(&mut stage_durations, "prepare_cg_data", || {
+            self.prepare_cg_data()
+        })?;
+        timed(&mut stage_durations, "create_output", || {
+            self.create_output()
+        })?;
+        timed(&mut stage_durations, "filter_unused_code", || {
+            self.filter_unused_code()
+        })?;
+        let output = timed(&mut stage_durations, "cleanup_cg_data", || {
+            self.cleanup_cg_data()
+        })?;
+        self.stage_durations = stage_durations;
+        if self.options.stats {
+            progress!(self.options, "{}", self.stats_table());
         }
-        // delete working tmp dir
-        fs::remove_dir_all(self.tmp_dir.as_path())?;
         Ok(output)
     }
+    /// Table of durations of all processing stages of last run.
+    pub fn stats_table(&self) -> String {
+        let mut table = format!("{:<20} {:>12}", "stage", "duration");
+        for (stage, duration) in self.stage_durations.iter() {
+            table += &format!(
+                "\n{:<20} {:>9.3} ms",
+                stage,
+                duration.as_secs_f64() * 1000.0
+            );
+        }
+        table
+    }
+    /// Writes output returned by cleanup_cg_data to writer, if output is directed to stdout.
+    pub fn write_output(&self, output: &str, writer: &mut impl Write) -> BoxResult<()> {
+        if !self.options.simulate && self.options.output_to_stdout() {
+            writer.write_all(output.as_bytes())?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -269,7 +908,502 @@ mod tests {
     use super::*;
     use std::fs;
     use std::path::PathBuf;
-    use std::process::Command;
+
+    // compares output with golden file; set CSF_CG_REGENERATE_GOLDEN to overwrite golden file with output
+    fn assert_golden(output: &str, golden_file: &Path) {
+        if std::env::var_os("CSF_CG_REGENERATE_GOLDEN").is_some() {
+            fs::write(golden_file, output).unwrap();
+        }
+        let expected_output = fs::read_to_string(golden_file).unwrap();
+        assert_eq!(output, expected_output);
+    }
+
+    #[test]
+    fn test_extern_crate_alias_of_lib() {
+        let input = PathBuf::from(r"./test/extern_crate/src/main.rs");
+        let options = Cli {
+            deny_warnings: true,
+            line_ending: Some("lf".to_string()),
+            version_header: true,
+            ..test_cli(input, "shared")
+        };
+        let mut data = CGData::new(options);
+        let output = data.run().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+        assert_golden(
+            &output,
+            Path::new(r"./test/expected_test_results/extern_crate.rs"),
+        );
+    }
+
+    #[test]
+    fn test_blocked_module_required() {
+        let input = PathBuf::from(r"./test/block_modules/challenge/src/main.rs");
+        let options = Cli {
+            block_modules: "debug".to_string(),
+            deny_warnings: true,
+            line_ending: Some("lf".to_string()),
+            version_header: true,
+            ..test_cli(input, "toolbox")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.create_output().unwrap();
+        let err = data.filter_unused_code().unwrap_err();
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+        assert_eq!(
+            err.to_string(),
+            "blocked module \"debug\" is required by fused output; remove it from --block-modules"
+        );
+    }
+
+    #[test]
+    fn test_statistics() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.create_output().unwrap();
+        let stats = data.statistics().unwrap();
+        // optional dependency serde of csf_cg_lib_test is compiled, if feature "serde" is active
+        data.lib_features.insert("serde".into());
+        let stats_with_serde = data.statistics().unwrap();
+        let mut output = String::new();
+        data.load_output(&mut output).unwrap();
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+        // main.rs includes two local modules and uses four modules of csf_cg_lib_test
+        assert_eq!(stats.local_modules, 2);
+        assert_eq!(stats.lib_modules, 4);
+        assert_eq!(stats.supported_dependencies, 0);
+        assert_eq!(stats.unsupported_dependencies, 0);
+        assert_eq!(stats_with_serde.supported_dependencies, 0);
+        assert_eq!(stats_with_serde.unsupported_dependencies, 1);
+        // numbers of output change with code of lib
+        assert_eq!(stats.characters, output.chars().count());
+        assert_eq!(
+            stats.impl_blocks,
+            output.lines().filter(|l| l.starts_with("impl")).count()
+        );
+        assert!(stats.items > stats.impl_blocks);
+    }
+
+    #[test]
+    fn test_find_items_by_name() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.create_output().unwrap();
+        let lines = data.find_items_by_name("set").unwrap();
+        assert!(data
+            .find_items_by_name("csf_cg_no_such_item")
+            .unwrap()
+            .is_empty());
+        let mut output = String::new();
+        data.load_output(&mut output).unwrap();
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+        // MyArray and MyMap2D of csf_cg_lib_test both implement fn set()
+        let output: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let mut impl_blocks = Vec::new();
+        for line in lines {
+            assert!(output[line - 1].trim_start().starts_with("fn set("));
+            let impl_block = output[..line]
+                .iter()
+                .rev()
+                .find(|l| l.starts_with("impl"))
+                .unwrap();
+            impl_blocks.push(*impl_block);
+        }
+        assert!(impl_blocks.iter().any(|l| l.contains("MyArray<T, N>")));
+        assert!(impl_blocks
+            .iter()
+            .any(|l| l.contains("MyMap2D<T, X, Y, N>")));
+    }
+
+    #[test]
+    fn test_binary_target() {
+        let options = Cli {
+            deny_warnings: true,
+            line_ending: Some("lf".to_string()),
+            version_header: true,
+            bin: Some("second".to_string()),
+            ..test_cli(r"./test/two_bins", "shared")
+        };
+        let mut data = CGData::new(options.clone());
+        let output = data.run().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+        assert!(output.contains("//⏬second.rs"));
+        assert!(output.contains("fn greeting() -> &'static str {"));
+        assert!(!output.contains("\"default\""));
+
+        // explicit [[bin]] target
+        let found = find_binary_target(Path::new("./test/two_bins/Cargo.toml"), "alternative");
+        assert_eq!(
+            found.unwrap(),
+            PathBuf::from("./test/two_bins/src/bin/alt.rs")
+        );
+
+        let mut options = options;
+        options.bin = Some("third".to_string());
+        let mut data = CGData::new(options);
+        let err = data.prepare_cg_data().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "binary target \"third\" not found; available binary targets: alternative, second, two_bins"
+        );
+    }
+
+    #[test]
+    fn test_edition_2015() {
+        let options = Cli {
+            simulate: true,
+            ..test_cli(r"./test/edition_2015/src/main.rs", "legacy")
+        };
+        let mut data = CGData::new(options);
+        let err = data.prepare_cg_data().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "package \"legacy\" uses edition 2015, which is not supported; set edition to 2018 or later"
+        );
+
+        // missing edition defaults to 2015, inherited edition is never 2015
+        let manifest = "[package]\nname = \"legacy\"\n".parse::<Value>().unwrap();
+        assert!(is_edition_2015(&manifest));
+        let manifest = "[package]\nedition.workspace = true\n"
+            .parse::<Value>()
+            .unwrap();
+        assert!(!is_edition_2015(&manifest));
+    }
+
+    #[test]
+    fn test_output_is_byte_stable() {
+        // fusion of binary fixture with local and lib modules
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            simulate: true,
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        let fusions: Vec<String> = (0..2)
+            .map(|_| {
+                let mut data = CGData::new(options.clone());
+                data.prepare_cg_data().unwrap();
+                data.fusion_to_string().unwrap()
+            })
+            .collect();
+        assert_eq!(fusions[0], fusions[1]);
+
+        // filtered output of fixture with multiple lib modules
+        let mut options = options;
+        options.input = PathBuf::from(r"./test/block_modules/challenge/src/main.rs");
+        options.lib = "toolbox".to_string();
+        options.simulate = false;
+        let outputs: Vec<String> = (0..2)
+            .map(|_| CGData::new(options.clone()).run().unwrap())
+            .collect();
+        assert!(outputs[0].contains("fn manhattan("));
+        assert_eq!(outputs[0], outputs[1]);
+    }
+
+    #[test]
+    fn test_golden_workspace_member() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let options = Cli {
+            deny_warnings: true,
+            line_ending: Some("lf".to_string()),
+            version_header: true,
+            ..test_cli(input, "shared")
+        };
+        let mut data = CGData::new(options);
+        let output = data.run().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+        assert_golden(
+            &output,
+            Path::new(r"./test/expected_test_results/workspace_member.rs"),
+        );
+    }
+
+    #[test]
+    fn test_check_toolchain() {
+        check_toolchain("cargo").unwrap();
+        let err = check_toolchain("csf_cg_missing_tool").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "could not execute \"csf_cg_missing_tool --version\"; please make sure csf_cg_missing_tool is installed and in PATH"
+        );
+    }
+
+    #[test]
+    fn test_prepare_cg_data_with_missing_toolchain() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let mut data = CGData::new(test_cli(input, "shared"));
+        data.cargo = "csf_cg_missing_tool".to_string();
+        let err = data.prepare_cg_data().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CGError>(),
+            Some(CGError::MissingToolchain(tool)) if tool == "csf_cg_missing_tool"
+        ));
+        // preflight fails before crate is read and tmp dir is created
+        assert!(data.crate_name.is_empty());
+        assert_eq!(data.tmp_dir, PathBuf::new());
+    }
+
+    #[test]
+    fn test_workspace_member() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let options = Cli {
+            version_header: true,
+            ..test_cli(input, "shared")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        // inherited keys are resolved in manifest of tmp dir
+        let toml = fs::read_to_string(data.tmp_dir.join("Cargo.toml"))
+            .unwrap()
+            .parse::<Value>()
+            .unwrap();
+        assert_eq!(toml["package"]["version"].as_str(), Some("0.2.0"));
+        assert!(toml["dependencies"]["shared"]["path"].is_str());
+        assert!(data.my_lib.as_ref().unwrap().ends_with("shared/src"));
+
+        data.create_output().unwrap();
+        data.filter_unused_code().unwrap();
+        let mut file_content = String::new();
+        data.load_output(&mut file_content).unwrap();
+        assert!(file_content.starts_with("// fused crates: challenge 0.2.0, shared 0.2.0"));
+        assert!(file_content.contains("fn add("));
+        assert!(!file_content.contains("fn sub("));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_write_output_to_stdout() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let options = Cli {
+            verbose: true,
+            stdout: true,
+            ..test_cli(input, "shared")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.create_output().unwrap();
+        data.filter_unused_code().unwrap();
+        let output = data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+
+        // progress messages are not part of written output
+        let mut writer: Vec<u8> = Vec::new();
+        data.write_output(&output, &mut writer).unwrap();
+        let written = String::from_utf8(writer).unwrap();
+        assert!(written.contains("fn add("));
+        assert!(!written.contains("removing tmp dir..."));
+        syn::parse_file(&written).unwrap();
+    }
+
+    #[test]
+    fn test_stats_table() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let options = Cli {
+            stats: true,
+            ..test_cli(input, "shared")
+        };
+        let mut data = CGData::new(options);
+        let output = data.run().unwrap();
+        assert!(output.contains("fn add("));
+        assert!(!data.tmp_dir.is_dir());
+
+        let table = data.stats_table();
+        let mut lines = table.lines();
+        assert!(lines.next().unwrap().starts_with("stage"));
+        for stage in [
+            "prepare_cg_data",
+            "create_output",
+            "filter_unused_code",
+            "cleanup_cg_data",
+        ] {
+            let line = lines.next().unwrap();
+            let mut columns = line.split_whitespace();
+            assert_eq!(columns.next(), Some(stage));
+            let duration: f64 = columns.next().unwrap().parse().unwrap();
+            assert!(duration >= 0.0);
+            assert_eq!(columns.next(), Some("ms"));
+        }
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_strict_platform() {
+        let input = PathBuf::from(r"./test/unsupported_dependencies/src/main.rs");
+        let options = Cli {
+            strict_platform: true,
+            ..test_cli(input, "shared")
+        };
+        let mut data = CGData::new(options);
+        let err = data.prepare_cg_data().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "dependencies not supported by CodinGame: serde (required by unsupported_dependencies), \
+            bitflags (required by shared); remove them or run without --strict-platform"
+        );
+        // fail fast before creating tmp dir
+        assert_eq!(data.tmp_dir, PathBuf::new());
+        assert_eq!(
+            data.list_unsupported_dependencies().unwrap(),
+            vec![
+                "serde (required by unsupported_dependencies)".to_string(),
+                "bitflags (required by shared)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_active_lib_features() {
+        let lib_manifest = r#"
+            [package]
+            name = "shared"
+
+            [features]
+            default = ["grid"]
+            grid = ["dep:fixedbitset", "rand/std"]
+            hex = ["grid"]
+            debug = []
+        "#
+        .parse::<Value>()
+        .unwrap();
+        let dependency = r#"features = ["hex"]"#.parse::<Value>().unwrap();
+        assert_eq!(
+            active_lib_features(&dependency, &lib_manifest),
+            BTreeSet::from(["default", "fixedbitset", "grid", "hex"].map(String::from))
+        );
+        let dependency = r#"default-features = false"#.parse::<Value>().unwrap();
+        assert!(active_lib_features(&dependency, &lib_manifest).is_empty());
+    }
+
+    #[test]
+    fn test_active_dependencies() {
+        let manifest = r#"
+            [package]
+            name = "shared"
+
+            [dependencies]
+            itertools = "0.13"
+            fixedbitset = { version = "0.4", optional = true }
+            rand = { version = "0.8", optional = true }
+            serde = { version = "1.0", optional = true }
+
+            [features]
+            grid = ["dep:fixedbitset", "rand/std", "serde?/std"]
+        "#
+        .parse::<Value>()
+        .unwrap();
+        assert_eq!(
+            active_dependencies(&manifest, &BTreeSet::new()),
+            vec!["itertools".to_string()]
+        );
+        let features = BTreeSet::from(["fixedbitset", "grid"].map(String::from));
+        assert_eq!(
+            active_dependencies(&manifest, &features),
+            ["fixedbitset", "itertools", "rand"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn test_simulate_without_writing_files() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let options = Cli {
+            simulate: true,
+            ..test_cli(input, "shared")
+        };
+        let list_files = |dir: &Path| {
+            let mut files: Vec<PathBuf> = fs::read_dir(dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect();
+            files.sort();
+            files
+        };
+        let workspace_dir = PathBuf::from(r"./test/workspace");
+        let challenge_bin_dir = workspace_dir.join("challenge").join("src").join("bin");
+        let files_before = list_files(&workspace_dir);
+        let mut data = CGData::new(options);
+        data.run().unwrap();
+        assert!(!data.tmp_dir.exists());
+        assert!(!challenge_bin_dir.exists());
+        assert_eq!(list_files(&workspace_dir), files_before);
+        assert_eq!(
+            data.simulation_summary().unwrap(),
+            format!(
+                "summary: 0 local module(s), 1 lib module(s), 0 external dependencies, {} characters before filtering unused code",
+                data.fusion_size()
+            )
+        );
+        assert!(data.fusion_size() > 0);
+    }
+
+    #[test]
+    fn test_deterministic_temp() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let options = Cli {
+            deterministic_temp: true,
+            ..test_cli(input, "shared")
+        };
+        let mut tmp_paths = Vec::new();
+        for _ in 0..2 {
+            let mut data = CGData::new(options.clone());
+            data.prepare_cg_data().unwrap();
+            assert!(data.tmp_dir.is_dir());
+            tmp_paths.push((data.tmp_dir.clone(), data.tmp_output_file.clone()));
+            data.create_output().unwrap();
+            data.cleanup_cg_data().unwrap();
+            assert!(!data.tmp_dir.is_dir());
+        }
+        // same names in repeated runs
+        assert_eq!(tmp_paths[0], tmp_paths[1]);
+        // name is fixed hash of crate name and bin name
+        let tmp_name = tmp_paths[0].0.file_name().unwrap().to_str().unwrap();
+        assert_eq!(tmp_name, "csf_cg_d67100261fa5f43b");
+        assert!(tmp_paths[0].1.ends_with(format!("{}.rs", tmp_name)));
+
+        // existing dir without marker file is not removed
+        fs::create_dir_all(&tmp_paths[0].0).unwrap();
+        let mut data = CGData::new(options);
+        let err = data.prepare_cg_data().unwrap_err();
+        assert!(tmp_paths[0].0.is_dir());
+        fs::remove_dir_all(&tmp_paths[0].0).unwrap();
+        assert!(err.to_string().contains("was not created by csf_cg"));
+    }
+
+    #[test]
+    fn test_supported_crates_of_config_file() {
+        let input = PathBuf::from(r"./test/supported_crates/src/main.rs");
+        let options = Cli {
+            strict_platform: true,
+            supported_crates: "serde;rand".to_string(),
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        let err = data.prepare_cg_data().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "dependencies not supported by CodinGame: nalgebra (required by supported_crates); \
+            remove them or run without --strict-platform"
+        );
+        // union of CodinGame crates, config file and cli option
+        let supported_crates = data.supported_crates().unwrap();
+        for supported_crate in CODINGAME_SUPPORTED_CRATES
+            .iter()
+            .chain(["bitflags", "fixedbitset", "serde"].iter())
+        {
+            assert!(supported_crates.contains(&supported_crate.to_string()));
+        }
+        assert_eq!(supported_crates.len(), CODINGAME_SUPPORTED_CRATES.len() + 3);
+    }
 
     #[test]
     fn test_generating_output() {
@@ -282,11 +1416,35 @@ mod tests {
             challenge_only: false,
             modules: "all".to_string(),
             block_hidden: "my_compass;my_array".to_string(),
+            block_modules: "".to_string(),
             lib: "csf_cg_lib_test".to_string(),
             verbose: true,
             simulate: false,
             del_comments: false,
             keep_empty_lines: false,
+            strip_panic_messages: false,
+            deny_warnings: false,
+            line_ending: None,
+            version_header: false,
+            keep_visibility: false,
+            libs_only: false,
+            group_uses: false,
+            no_marker: false,
+            script_shebang: false,
+            purge_with: "check".to_string(),
+            module_order_file: None,
+            stdout: false,
+            stats: false,
+            size_limit: 100_000,
+            minify: false,
+            keep_doc_comments: None,
+            strict_platform: false,
+            deterministic_temp: false,
+            supported_crates: "".to_string(),
+            purge_rounds: None,
+            bin: None,
+            purge_report: None,
+            print_config: false,
         };
         // prepare output
         let mut data = CGData::new(options);
@@ -352,11 +1510,35 @@ mod tests {
             challenge_only: false,
             modules: "all".to_string(),
             block_hidden: "my_compass;my_array".to_string(),
+            block_modules: "".to_string(),
             lib: "csf_cg_lib_test".to_string(),
             verbose: true,
             simulate: false,
             del_comments: true,
             keep_empty_lines: false,
+            strip_panic_messages: false,
+            deny_warnings: false,
+            line_ending: None,
+            version_header: false,
+            keep_visibility: false,
+            libs_only: false,
+            group_uses: false,
+            no_marker: false,
+            script_shebang: false,
+            purge_with: "check".to_string(),
+            module_order_file: None,
+            stdout: false,
+            stats: false,
+            size_limit: 100_000,
+            minify: false,
+            keep_doc_comments: None,
+            strict_platform: false,
+            deterministic_temp: false,
+            supported_crates: "".to_string(),
+            purge_rounds: None,
+            bin: None,
+            purge_report: None,
+            print_config: false,
         };
 
         // prepare output
@@ -389,11 +1571,35 @@ mod tests {
             modules: "all".to_string(),
             //block_hidden: "my_array;my_line;my_rectangle".to_string(),
             block_hidden: "".to_string(),
+            block_modules: "".to_string(),
             lib: "my_lib".to_string(),
             verbose: false,
             simulate: false,
             del_comments: false,
             keep_empty_lines: true,
+            strip_panic_messages: false,
+            deny_warnings: false,
+            line_ending: None,
+            version_header: false,
+            keep_visibility: false,
+            libs_only: false,
+            group_uses: false,
+            no_marker: false,
+            script_shebang: false,
+            purge_with: "check".to_string(),
+            module_order_file: None,
+            stdout: false,
+            stats: false,
+            size_limit: 100_000,
+            minify: false,
+            keep_doc_comments: None,
+            strict_platform: false,
+            deterministic_temp: false,
+            supported_crates: "".to_string(),
+            purge_rounds: None,
+            bin: None,
+            purge_report: None,
+            print_config: false,
         };
 
         // prepare output
@@ -411,7 +1617,7 @@ mod tests {
             Command::new(command)
                 .arg(".")
                 .current_dir(data.tmp_dir.as_path())
-                .spawn()
+                .status()
                 .unwrap();
         }
 
@@ -17,10 +17,13 @@ fn main() {
 }
 
 fn run(options: Cli) -> BoxResult<()> {
+    let print_config = options.print_config;
     let mut data = CGData::new(options);
-    data.prepare_cg_data()?;
-    data.create_output()?;
-    data.filter_unused_code()?;
-    data.cleanup_cg_data()?;
+    if print_config {
+        print!("{}", data.effective_config()?);
+        return Ok(());
+    }
+    let output = data.run()?;
+    data.write_output(&output, &mut std::io::stdout())?;
     Ok(())
 }
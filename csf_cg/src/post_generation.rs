@@ -2,10 +2,16 @@ use cargo_metadata::{
     diagnostic::{DiagnosticCode, DiagnosticLevel, DiagnosticSpan},
     Message,
 };
+use quote::ToTokens;
+use serde::Serialize;
 use std::collections::BTreeMap;
 use std::fs;
+use std::path::Path;
 use std::process::Command;
 use std::process::Output;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use toml::Value;
 
 use super::*;
 use crate::configuration::*;
@@ -150,17 +156,349 @@ impl<'a> NameSpace<'a> {
     }
 }
 
+const VERSION_HEADER_START: &str = "// fused crates: ";
+
+// deletes comments except file markers. Doc comments are kept depending on keep_doc_comments:
+// "all" keeps outer (///) and inner (//!) doc comments, "crate" keeps only inner doc comments.
+fn delete_comments(code: &str, keep_doc_comments: Option<&str>, line_end_chars: &str) -> String {
+    code.lines()
+        .map(|l| {
+            let trimmed = l.trim_start();
+            let keep_line = l.contains(&['⏬', '⏫'][..])
+                || match keep_doc_comments {
+                    Some("all") => trimmed.starts_with("///") || trimmed.starts_with("//!"),
+                    Some("crate") => trimmed.starts_with("//!"),
+                    _ => false,
+                };
+            if keep_line {
+                l
+            } else {
+                match l.split_once("//") {
+                    Some((pre_split, _)) => pre_split.trim_end(),
+                    None => l,
+                }
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join(line_end_chars)
+}
+
+// prints every item of code on one line from its tokens. Literals are printed unchanged,
+// while comments and file markers are removed and doc comments are turned into attributes.
+fn minify(code: &str, line_end_chars: &str) -> BoxResult<String> {
+    let file = syn::parse_file(code)?;
+    let lines: Vec<String> = file
+        .attrs
+        .iter()
+        .map(|a| a.to_token_stream().to_string())
+        .chain(file.items.iter().map(|i| i.to_token_stream().to_string()))
+        .collect();
+    Ok(lines.join(line_end_chars))
+}
+
+// collects first segment and all modules of paths, which start with name of lib or crate
+struct LibPathVisitor<'a> {
+    lib: &'a str,
+    paths: Vec<Vec<String>>,
+}
+
+impl LibPathVisitor<'_> {
+    fn push_path(&mut self, path: Vec<String>) {
+        if path.first().is_some_and(|p| p == self.lib || p == "crate") {
+            self.paths.push(path);
+        }
+    }
+    fn collect_use_tree(&mut self, tree: &syn::UseTree, mut path: Vec<String>) {
+        match tree {
+            syn::UseTree::Path(use_path) => {
+                path.push(use_path.ident.to_string());
+                self.collect_use_tree(&use_path.tree, path);
+            }
+            syn::UseTree::Group(use_group) => {
+                for item in use_group.items.iter() {
+                    self.collect_use_tree(item, path.clone());
+                }
+            }
+            syn::UseTree::Name(name) => {
+                path.push(name.ident.to_string());
+                self.push_path(path);
+            }
+            syn::UseTree::Rename(rename) => {
+                path.push(rename.ident.to_string());
+                self.push_path(path);
+            }
+            syn::UseTree::Glob(_) => self.push_path(path),
+        }
+    }
+}
+
+impl<'ast> syn::visit::Visit<'ast> for LibPathVisitor<'_> {
+    fn visit_item_use(&mut self, i: &'ast syn::ItemUse) {
+        self.collect_use_tree(&i.tree, Vec::new());
+    }
+    fn visit_path(&mut self, i: &'ast syn::Path) {
+        self.push_path(i.segments.iter().map(|s| s.ident.to_string()).collect());
+        syn::visit::visit_path(self, i);
+    }
+}
+
+// returns error, if code still refers to a blocked module of lib after filtering unused code.
+// Since blocked modules are not fused, such a path cannot be resolved on CodinGame.
+fn check_blocked_modules(code: &str, lib: &str, block_modules: &str) -> BoxResult<()> {
+    // output, which cannot be parsed, is reported by cargo check later on
+    let Ok(syntax) = syn::parse_file(code) else {
+        return Ok(());
+    };
+    let mut visitor = LibPathVisitor {
+        lib,
+        paths: Vec::new(),
+    };
+    syn::visit::Visit::visit_file(&mut visitor, &syntax);
+    for blocked_module in block_modules.split(';').filter(|b| !b.is_empty()) {
+        if visitor
+            .paths
+            .iter()
+            .any(|path| path[1..].iter().any(|p| p == blocked_module))
+        {
+            return Err(Box::new(CGError::BlockedModuleRequired(
+                blocked_module.into(),
+            )));
+        }
+    }
+    Ok(())
+}
+
+// entry of purge report, which describes one cargo check message and its applied fix
+#[derive(Serialize)]
+struct PurgeReportEntry {
+    round: usize,
+    line: usize,
+    action: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    message: String,
+}
+
+impl PurgeReportEntry {
+    fn new(round: usize, message: &CargoCheckItem, patch_action: &PatchAction) -> Self {
+        let (action, line) = match patch_action {
+            PatchAction::SnipNameSpace(line) => ("snip", *line),
+            PatchAction::AdjustUnusedVariableName(line, _) => ("rename_unused_variable", *line),
+            PatchAction::SnipNeverConstructedEnumVariant(line) => ("snip_enum_variant", *line),
+        };
+        Self {
+            round,
+            line,
+            action,
+            code: message.code.as_ref().map(|c| c.code.clone()),
+            message: message.message.clone(),
+        }
+    }
+}
+
+// reads package name and version from Cargo.toml in given dir
+fn read_package_version(package_dir: &Path) -> BoxResult<String> {
+    let mut toml = fs::read_to_string(package_dir.join("Cargo.toml"))?.parse::<Value>()?;
+    if let Some((workspace_dir, workspace)) = find_workspace_root(package_dir)? {
+        resolve_workspace_inheritance(&mut toml, &workspace_dir, &workspace);
+    }
+    let package = toml.get("package").and_then(|p| p.as_table());
+    let name = package
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or_default();
+    let version = package
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    Ok(format!("{} {}", name, version))
+}
+
+const SCRIPT_SHEBANG: &str = "#!/usr/bin/env rust-script";
+const SCRIPT_MANIFEST_START: &str = "//! ```cargo";
+const SCRIPT_MANIFEST_END: &str = "//! ```";
+
+// reads name and version of all non optional dependencies from Cargo.toml in given dir,
+// ignoring excluded_dependency (the local lib, which is fused into output)
+fn read_dependencies(
+    package_dir: &Path,
+    excluded_dependency: &str,
+) -> BoxResult<Vec<(String, String)>> {
+    let toml = fs::read_to_string(package_dir.join("Cargo.toml"))?.parse::<Value>()?;
+    let mut dependencies = Vec::new();
+    if let Some(table) = toml.get("dependencies").and_then(|d| d.as_table()) {
+        for (name, dependency) in table.iter() {
+            if name == excluded_dependency {
+                continue;
+            }
+            let version = match dependency {
+                Value::String(version) => version.as_str(),
+                Value::Table(dependency) => {
+                    if dependency.get("optional").and_then(|o| o.as_bool()) == Some(true) {
+                        continue;
+                    }
+                    match dependency.get("version").and_then(|v| v.as_str()) {
+                        Some(version) => version,
+                        // path or git dependencies without version cannot be resolved by a script runner
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            dependencies.push((name.to_owned(), version.to_owned()));
+        }
+    }
+    Ok(dependencies)
+}
+
+// removes shebang and cargo manifest created by script_header() from start of code
+fn remove_script_header(code: &str) -> &str {
+    let Some(remaining) = code.strip_prefix(SCRIPT_SHEBANG) else {
+        return code;
+    };
+    let remaining = remaining.trim_start_matches(['\r', '\n']);
+    if !remaining.starts_with(SCRIPT_MANIFEST_START) {
+        return remaining;
+    }
+    match remaining.split_once(&format!("\n{}", SCRIPT_MANIFEST_END)) {
+        Some((_, code)) => code.trim_start_matches(['\r', '\n']),
+        None => remaining,
+    }
+}
+
+// replaces all line endings of code with line_end_chars
+fn normalize_line_endings(code: &str, line_end_chars: &str) -> String {
+    let mut output = code.lines().collect::<Vec<&str>>().join(line_end_chars);
+    if code.ends_with('\n') {
+        output.push_str(line_end_chars);
+    }
+    output
+}
+
+// macros, which may carry a panic message, and number of leading arguments,
+// which are no message and therefore must be kept
+const PANIC_MESSAGE_MACROS: [(&str, usize); 10] = [
+    ("panic", 0),
+    ("unreachable", 0),
+    ("todo", 0),
+    ("unimplemented", 0),
+    ("assert", 1),
+    ("assert_eq", 2),
+    ("assert_ne", 2),
+    ("debug_assert", 1),
+    ("debug_assert_eq", 2),
+    ("debug_assert_ne", 2),
+];
+
+/// Post transform replacing messages of panic!, unreachable! and assert macros with an empty string, e.g.
+/// panic!("line {}", line!()) -> panic!("")
+/// assert_eq!(a, b, "{} != {}", a, b) -> assert_eq!(a, b, "")
+pub(crate) struct StripPanicMessages;
+
+impl Fold for StripPanicMessages {
+    fn fold_macro(&mut self, mut i: syn::Macro) -> syn::Macro {
+        let Some((_, keep)) = PANIC_MESSAGE_MACROS
+            .iter()
+            .find(|(name, _)| i.path.is_ident(name))
+        else {
+            return i;
+        };
+        let Ok(arguments) =
+            Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated.parse2(i.tokens.clone())
+        else {
+            return i;
+        };
+        if arguments.len() > *keep {
+            let kept = arguments.iter().take(*keep);
+            i.tokens = quote::quote!(#(#kept,)* "");
+        }
+        i
+    }
+}
+
+// splits a single line use statement without braces into indent and keyword, path root and item, e.g.
+// "    use my_lib::my_map::MyMap;" -> ("    use ", "my_lib::my_map", "MyMap")
+fn split_simple_use(line: &str) -> Option<(&str, &str, &str)> {
+    let line = line.trim_end();
+    let trimmed = line.trim_start();
+    let keyword_len = if trimmed.starts_with("pub use ") {
+        "pub use ".len()
+    } else if trimmed.starts_with("use ") {
+        "use ".len()
+    } else {
+        return None;
+    };
+    let path = trimmed[keyword_len..].strip_suffix(';')?;
+    if path.contains('{') {
+        return None;
+    }
+    let (root, item) = path.rsplit_once("::")?;
+    let head_len = line.len() - trimmed.len() + keyword_len;
+    Some((&line[..head_len], root.trim(), item.trim()))
+}
+
+// groups consecutive use statements with same root into one use statement, e.g.
+// use a::B; use a::C; -> use a::{B, C};
+fn group_use_statements(code: &str, line_end_chars: &str) -> String {
+    let lines: Vec<&str> = code.lines().collect();
+    let mut output: Vec<String> = Vec::with_capacity(lines.len());
+    let mut index = 0;
+    while index < lines.len() {
+        // collect all items of consecutive use statements by indent, keyword and root
+        let mut groups: Vec<(&str, &str, Vec<&str>)> = Vec::new();
+        while let Some((head, root, item)) = lines.get(index).and_then(|l| split_simple_use(l)) {
+            match groups.iter_mut().find(|(h, r, _)| *h == head && *r == root) {
+                Some((_, _, items)) => {
+                    if !items.contains(&item) {
+                        items.push(item);
+                    }
+                }
+                None => groups.push((head, root, vec![item])),
+            }
+            index += 1;
+        }
+        if groups.is_empty() {
+            output.push(lines[index].to_string());
+            index += 1;
+            continue;
+        }
+        for (head, root, items) in groups {
+            if items.len() == 1 {
+                output.push(format!("{}{}::{};", head, root, items[0]));
+            } else {
+                output.push(format!("{}{}::{{{}}};", head, root, items.join(", ")));
+            }
+        }
+    }
+    let mut output = output.join(line_end_chars);
+    if code.ends_with('\n') {
+        output.push_str(line_end_chars);
+    }
+    output
+}
+
+// clippy lints, which point to items, which can be purged completely.
+// All other clippy lints are allowed, since they cannot be fixed by snipping code.
+const CLIPPY_PURGE_LINTS: [&str; 2] = ["clippy::empty_drop", "clippy::partialeq_ne_impl"];
+
 impl CGData {
     pub fn command_cargo_check(&self) -> BoxResult<Output> {
         let current_dir = fs::canonicalize(self.tmp_dir.as_path())?;
         let bin_name = self.tmp_output_file.file_stem().unwrap().to_str().unwrap();
-        Ok(Command::new("cargo")
+        let mut command = Command::new(&self.cargo);
+        command
             .current_dir(current_dir)
-            .arg("check")
+            .arg(self.options.purge_with.as_str())
             .arg("--bin")
             .arg(bin_name)
-            .arg("--message-format=json")
-            .output()?)
+            .arg("--message-format=json");
+        if self.options.purge_with == "clippy" {
+            command.arg("--").arg("-A").arg("clippy::all");
+            for lint in CLIPPY_PURGE_LINTS.iter() {
+                command.arg("-W").arg(lint);
+            }
+        }
+        Ok(command.output()?)
     }
     fn collect_cargo_check_compiler_messages(
         &self,
@@ -219,9 +557,14 @@ impl CGData {
         match patch_action {
             PatchAction::AdjustUnusedVariableName(_, _) => {
                 if self.options.verbose {
-                    println!(
+                    progress!(
+                        self.options,
                         "[{} {}] adjusting cargo check message \"{}\" (line_start: {}, byte_start: {})",
-                        verbose_start, error_code, message.message, message.span.line_start, message.span.byte_start
+                        verbose_start,
+                        error_code,
+                        message.message,
+                        message.span.line_start,
+                        message.span.byte_start
                     );
                 }
                 PatchAction::AdjustUnusedVariableName(
@@ -231,18 +574,26 @@ impl CGData {
             }
             PatchAction::SnipNameSpace(_) => {
                 if self.options.verbose {
-                    println!(
+                    progress!(
+                        self.options,
                         "[{} {}] filtering cargo check message \"{}\" (line_start: {})",
-                        verbose_start, error_code, message.message, message.span.line_start
+                        verbose_start,
+                        error_code,
+                        message.message,
+                        message.span.line_start
                     );
                 }
                 PatchAction::SnipNameSpace(message.span.line_start)
             }
             PatchAction::SnipNeverConstructedEnumVariant(_) => {
                 if self.options.verbose {
-                    println!(
+                    progress!(
+                        self.options,
                         "[{} {}] filtering never constructed variant \"{}\" (line_start: {})",
-                        verbose_start, error_code, message.message, message.span.line_start
+                        verbose_start,
+                        error_code,
+                        message.message,
+                        message.span.line_start
                     );
                 }
                 PatchAction::SnipNeverConstructedEnumVariant(message.span.line_start)
@@ -266,7 +617,7 @@ impl CGData {
         let (new_output, filtered) = name_space.filter_name_space();
         *output = new_output;
         if self.options.verbose {
-            println!("SNIP\n{}\nSNAP", filtered);
+            progress!(self.options, "SNIP\n{}\nSNAP", filtered);
         }
         Ok(())
     }
@@ -304,16 +655,86 @@ impl CGData {
         }
         // save enum variant to later remove match arms, which use never constructed variant, if any remain
         never_constructed_variants.push(enum_variant);
-        
+
         if self.options.verbose {
-            println!("SNIP\n{}\nSNAP", filtered);
+            progress!(self.options, "SNIP\n{}\nSNAP", filtered);
         }
-        
+
         // join lines for new output
         *output = lines.join(self.line_end_chars.as_str());
         Ok(())
     }
 
+    // stores size of output and returns warning, if size exceeds size limit
+    fn check_size_limit(&self, output: &str) -> Option<String> {
+        let size = output.chars().count();
+        self.fusion_size.set(size);
+        if size > self.options.size_limit {
+            Some(format!(
+                "warning: output has {} characters ({} bytes), which exceeds size limit of {} characters",
+                size,
+                output.len(),
+                self.options.size_limit
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn apply_post_transforms(&self, output: &str) -> BoxResult<String> {
+        let mut file = syn::parse_file(output)?;
+        for transform in self.post_transforms.borrow_mut().iter_mut() {
+            file = transform.fold_file(file);
+        }
+        Ok(prettyplease::unparse(&file)
+            .lines()
+            .collect::<Vec<&str>>()
+            .join(self.line_end_chars.as_str()))
+    }
+
+    fn version_header(&self) -> BoxResult<String> {
+        let mut packages = vec![read_package_version(&self.crate_dir)?];
+        if let Some(ref my_lib) = self.my_lib {
+            packages.push(read_package_version(my_lib.parent().unwrap())?);
+        }
+        Ok(format!("{}{}", VERSION_HEADER_START, packages.join(", ")))
+    }
+
+    fn script_header(&self) -> BoxResult<String> {
+        let mut dependencies = read_dependencies(&self.tmp_dir, self.options.lib.as_str())?;
+        if let Some(ref my_lib) = self.my_lib {
+            for dependency in read_dependencies(my_lib.parent().unwrap(), "")? {
+                if !dependencies.iter().any(|(name, _)| *name == dependency.0) {
+                    dependencies.push(dependency);
+                }
+            }
+        }
+        let mut header = vec![
+            SCRIPT_SHEBANG.to_string(),
+            SCRIPT_MANIFEST_START.to_string(),
+            "//! [dependencies]".to_string(),
+        ];
+        for (name, version) in dependencies.iter() {
+            header.push(format!("//! {} = \"{}\"", name, version));
+        }
+        header.push(SCRIPT_MANIFEST_END.to_string());
+        Ok(header.join(self.line_end_chars.as_str()))
+    }
+
+    pub fn deny_remaining_warnings(&self) -> BoxResult<()> {
+        if self.options.verbose {
+            progress!(self.options, "checking output for remaining warnings...");
+        }
+        if let Some(message_collection) = self.collect_cargo_check_compiler_messages()? {
+            let messages: Vec<String> = message_collection
+                .values()
+                .map(|m| format!("line {}: {}", m.span.line_start, m.message))
+                .collect();
+            return Err(Box::new(CGError::RemainingWarnings(messages)));
+        }
+        Ok(())
+    }
+
     fn adjust_unused_variable_name(
         &self,
         output: &mut String,
@@ -321,18 +742,26 @@ impl CGData {
         byte_start: usize,
     ) {
         if self.options.verbose {
-            println!("OLD: {}", output.lines().nth(line_start - 1).unwrap());
+            progress!(
+                self.options,
+                "OLD: {}",
+                output.lines().nth(line_start - 1).unwrap()
+            );
         }
         output.insert(byte_start, '_');
         if self.options.verbose {
-            println!("NEW: {}", output.lines().nth(line_start - 1).unwrap());
+            progress!(
+                self.options,
+                "NEW: {}",
+                output.lines().nth(line_start - 1).unwrap()
+            );
         }
     }
 
     pub fn filter_unused_code(&self) -> BoxResult<()> {
         if !self.options.simulate {
             if self.options.verbose {
-                println!("starting filtering unused code in output...");
+                progress!(self.options, "starting filtering unused code in output...");
             }
             // use check_counter to prevent endless checking results
             let mut check_counter = 0;
@@ -344,59 +773,105 @@ impl CGData {
             // By reverse iteration through message_collection the fixes can be applied from bottom to top.
             // When all fixes are applied, the file is saved and a new round is started, until no more
             // messages are collected.
-            while let Some(message_collection) = self.collect_cargo_check_compiler_messages()? {
-                let mut output = String::new();
-                self.load_output(&mut output)?;
-                // revers iteration of message_collection, which results to work through messages from bottom to top
-                for (_, message) in message_collection.iter().rev() {
-                    if check_counter >= max_check_counter {
+            // without main.rs nothing is used, therefore filtering would remove all lib code
+            if !self.options.libs_only {
+                let mut purge_round = 0;
+                let mut purge_report: Vec<PurgeReportEntry> = Vec::new();
+                while let Some(message_collection) = self.collect_cargo_check_compiler_messages()? {
+                    if Some(purge_round) == self.options.purge_rounds {
+                        progress!(
+                            self.options,
+                            "stopped filtering after {} round(s); items to purge in next round:",
+                            purge_round
+                        );
+                        for (line, message) in message_collection.iter() {
+                            progress!(self.options, "line {}: {}", line, message.message);
+                        }
                         break;
                     }
-                    check_counter += 1;
-                    println!("check_counter: {}", check_counter);
-                    // ToDo: Debug stuff. remove later
-                    if message.level == DiagnosticLevel::Warning {
-                        //break
-                    }
+                    purge_round += 1;
+                    let mut output = String::new();
+                    self.load_output(&mut output)?;
+                    // revers iteration of message_collection, which results to work through messages from bottom to top
+                    for (_, message) in message_collection.iter().rev() {
+                        if check_counter >= max_check_counter {
+                            break;
+                        }
+                        check_counter += 1;
+                        progress!(self.options, "check_counter: {}", check_counter);
+                        // ToDo: Debug stuff. remove later
+                        if message.level == DiagnosticLevel::Warning {
+                            //break
+                        }
 
-                    match self.analyze_cargo_check_compiler_message(message) {
-                        PatchAction::AdjustUnusedVariableName(line_start, byte_start) => {
-                            self.adjust_unused_variable_name(&mut output, line_start, byte_start)
+                        let patch_action = self.analyze_cargo_check_compiler_message(message);
+                        if self.options.purge_report.is_some() {
+                            purge_report.push(PurgeReportEntry::new(
+                                purge_round,
+                                message,
+                                &patch_action,
+                            ));
                         }
-                        PatchAction::SnipNameSpace(line_start) => self.snip_name_space(
-                            &mut output,
-                            line_start,
-                            &never_constructed_variants,
-                        )?,
-                        PatchAction::SnipNeverConstructedEnumVariant(line_start) => self
-                            .snip_never_constructed_enum_variant(
+                        match patch_action {
+                            PatchAction::AdjustUnusedVariableName(line_start, byte_start) => self
+                                .adjust_unused_variable_name(&mut output, line_start, byte_start),
+                            PatchAction::SnipNameSpace(line_start) => self.snip_name_space(
                                 &mut output,
                                 line_start,
-                                &mut never_constructed_variants,
+                                &never_constructed_variants,
                             )?,
+                            PatchAction::SnipNeverConstructedEnumVariant(line_start) => self
+                                .snip_never_constructed_enum_variant(
+                                    &mut output,
+                                    line_start,
+                                    &mut never_constructed_variants,
+                                )?,
+                        }
                     }
-                }
 
-                self.save_output(&output)?;
+                    self.save_output(&output)?;
+                }
+                if let Some(ref path) = self.options.purge_report {
+                    if self.options.verbose {
+                        progress!(
+                            self.options,
+                            "writing purge report to {}...",
+                            path.display()
+                        );
+                    }
+                    let report = serde_json::json!({ "purged": purge_report });
+                    fs::write(path, serde_json::to_string_pretty(&report)?)?;
+                }
             }
             let mut output = String::new();
             self.load_output(&mut output)?;
+            check_blocked_modules(&output, &self.options.lib, &self.options.block_modules)?;
+            // applying registered folds
+            if !self.post_transforms.borrow().is_empty() {
+                if self.options.verbose {
+                    progress!(self.options, "applying post transforms...");
+                }
+                output = self.apply_post_transforms(&output)?;
+            }
             // removing comments, if option is set
             if self.options.del_comments {
                 if self.options.verbose {
-                    println!("deleting comments...");
+                    progress!(self.options, "deleting comments...");
                 }
+                output = delete_comments(
+                    &output,
+                    self.options.keep_doc_comments.as_deref(),
+                    self.line_end_chars.as_str(),
+                );
+            }
+            // removing file markers, if option is set
+            if self.options.no_marker {
+                progress!(self.options, "warning: removing file markers; output cannot be updated with insert options afterwards");
                 output = output
                     .lines()
-                    .map(|l| {
-                        if !l.contains(&['⏬', '⏫'][..]) {
-                            match l.split_once("//") {
-                                Some((pre_split, _)) => pre_split.trim_end(),
-                                None => l,
-                            }
-                        } else {
-                            l
-                        }
+                    .filter(|l| {
+                        let l = l.trim_start();
+                        !(l.starts_with("//⏬") || l.starts_with("//⏫"))
                     })
                     .collect::<Vec<&str>>()
                     .join(self.line_end_chars.as_str());
@@ -404,16 +879,799 @@ impl CGData {
             if !self.options.keep_empty_lines {
                 // deleting empty lines
                 if self.options.verbose {
-                    println!("deleting empty lines...");
+                    progress!(self.options, "deleting empty lines...");
                 }
                 output = output
                     .lines()
                     .filter(|l| !l.trim().is_empty())
                     .collect::<Vec<&str>>()
                     .join(self.line_end_chars.as_str());
-                self.save_output(&output)?;
+            }
+            // grouping use statements, if option is set
+            if self.options.group_uses {
+                if self.options.verbose {
+                    progress!(self.options, "grouping use statements...");
+                }
+                output = group_use_statements(&output, self.line_end_chars.as_str());
+            }
+            // script header must be first line of output and is therefore always added after version header
+            output = remove_script_header(&output).to_string();
+            // minifying output, if option is set
+            if self.options.minify {
+                if self.options.verbose {
+                    progress!(self.options, "minifying output...");
+                }
+                output = minify(&output, self.line_end_chars.as_str())?;
+            }
+            // adding or updating version header, if option is set
+            if self.options.version_header {
+                if self.options.verbose {
+                    progress!(self.options, "adding version header...");
+                }
+                let header = self.version_header()?;
+                output = match output.split_once(self.line_end_chars.as_str()) {
+                    Some((first_line, remaining))
+                        if first_line.starts_with(VERSION_HEADER_START) =>
+                    {
+                        header + self.line_end_chars.as_str() + remaining
+                    }
+                    _ => header + self.line_end_chars.as_str() + &output,
+                };
+            }
+            // adding script header, if option is set
+            if self.options.script_shebang {
+                if self.options.verbose {
+                    progress!(self.options, "adding script shebang and cargo manifest...");
+                }
+                output = self.script_header()? + self.line_end_chars.as_str() + &output;
+            }
+            // lib modules may use other line endings than main.rs
+            if self.options.line_ending.is_some() {
+                if self.options.verbose {
+                    progress!(self.options, "normalizing line endings...");
+                }
+                output = normalize_line_endings(&output, self.line_end_chars.as_str());
+            }
+            self.save_output(&output)?;
+            if let Some(warning) = self.check_size_limit(&output) {
+                progress!(self.options, "{}", warning);
+            }
+            if self.options.deny_warnings {
+                self.deny_remaining_warnings()?;
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_strip_panic_messages() {
+        let code = r#"fn check(x: usize, v: &[usize]) -> usize {
+    let text = "assert!(false, \"string stays untouched\")";
+    if x >= v.len() {
+        panic!("line {}, index {} out of range", line!(), x);
+    }
+    assert!(x > 0, "x must be positive");
+    assert_eq!(v[x], 2 * x, "{} != {}", v[x], 2 * x);
+    debug_assert_ne!(v[0], x);
+    match v[x] {
+        0 => unreachable!("zero is filtered before"),
+        _ => text.len() + v[x],
+    }
+}
+"#;
+        let expected = r#"fn check(x: usize, v: &[usize]) -> usize {
+    let text = "assert!(false, \"string stays untouched\")";
+    if x >= v.len() {
+        panic!("");
+    }
+    assert!(x > 0, "");
+    assert_eq!(v[x], 2 * x, "");
+    debug_assert_ne!(v[0], x);
+    match v[x] {
+        0 => unreachable!(""),
+        _ => text.len() + v[x],
+    }
+}
+"#;
+        let strip = |code: &str| {
+            let file = StripPanicMessages.fold_file(syn::parse_file(code).unwrap());
+            prettyplease::unparse(&file)
+        };
+        let stripped = strip(code);
+        assert_eq!(stripped, expected);
+        // stripping is idempotent
+        assert_eq!(strip(&stripped), expected);
+    }
+
+    #[test]
+    fn test_strip_multi_line_panic_message() {
+        let code = "fn f(c: char) {\n    assert!(\n        c != ')',\n        \"unexpected {} at {}\",\n        c,\n        line!(),\n    );\n}";
+        let file = StripPanicMessages.fold_file(syn::parse_file(code).unwrap());
+        assert_eq!(
+            prettyplease::unparse(&file),
+            "fn f(c: char) {\n    assert!(c != ')', \"\");\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_delete_comments() {
+        let code = r#"//! crate docs
+//⏬my_map.rs
+/// docs of Grid
+pub struct Grid; // trailing comment
+// plain comment
+//⏫my_map.rs"#;
+        assert_eq!(
+            delete_comments(code, None, "\n"),
+            "\n//⏬my_map.rs\n\npub struct Grid;\n\n//⏫my_map.rs"
+        );
+        assert_eq!(
+            delete_comments(code, Some("all"), "\n"),
+            "//! crate docs\n//⏬my_map.rs\n/// docs of Grid\npub struct Grid;\n\n//⏫my_map.rs"
+        );
+        assert_eq!(
+            delete_comments(code, Some("crate"), "\n"),
+            "//! crate docs\n//⏬my_map.rs\n\npub struct Grid;\n\n//⏫my_map.rs"
+        );
+    }
+
+    #[test]
+    fn test_group_use_statements() {
+        let code = r#"use std::collections::HashMap;
+use my_lib::my_map::MyMap;
+use std::collections::HashSet;
+use my_lib::my_map::MyMap;
+use std::fmt;
+use my_lib::my_point::{Point, Compass};
+mod inner {
+    use super::MyMap;
+    pub use std::fmt::Display;
+    pub use std::fmt::Debug as Dbg;
+    fn f() {}
+}
+use std::collections::BTreeMap;
+"#;
+        let expected = r#"use std::collections::{HashMap, HashSet};
+use my_lib::my_map::MyMap;
+use std::fmt;
+use my_lib::my_point::{Point, Compass};
+mod inner {
+    use super::MyMap;
+    pub use std::fmt::{Display, Debug as Dbg};
+    fn f() {}
+}
+use std::collections::BTreeMap;
+"#;
+        let grouped = group_use_statements(code, "\n");
+        assert_eq!(grouped, expected);
+        assert!(syn::parse_file(&grouped).is_ok());
+        // grouping is idempotent
+        assert_eq!(group_use_statements(&grouped, "\n"), expected);
+    }
+
+    #[test]
+    fn test_deny_remaining_warnings() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            deny_warnings: true,
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        // unused import results in a warning
+        data.save_output(&"use std::fmt;\n\nfn main() {}\n".to_string())
+            .unwrap();
+        let err = data.deny_remaining_warnings().unwrap_err();
+        assert!(err.to_string().contains("unused import"));
+
+        // warning free output passes
+        data.save_output(&"fn main() {}\n".to_string()).unwrap();
+        data.deny_remaining_warnings().unwrap();
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            keep_empty_lines: true,
+            line_ending: Some("crlf".to_string()),
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        assert_eq!(data.line_end_chars, "\r\n");
+
+        // output with mixed line endings
+        data.save_output(
+            &"fn main() {\n    let x = 1;\r\n\n    println!(\"{}\", x);\n}\n".to_string(),
+        )
+        .unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert_eq!(
+            output,
+            "fn main() {\r\n    let x = 1;\r\n\r\n    println!(\"{}\", x);\r\n}\r\n"
+        );
+
+        // switch to lf
+        data.options.line_ending = Some("lf".to_string());
+        data.line_end_chars = "\n".to_string();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert!(!output.contains('\r'));
+        assert_eq!(output.lines().count(), 5);
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_version_header() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            version_header: true,
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        data.save_output(&"fn main() {}".to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let expected =
+            "// fused crates: csf_cg_binary_test 0.1.0, csf_cg_lib_test 0.1.0\nfn main() {}";
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert_eq!(output, expected);
+
+        // existing header is replaced, not duplicated
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert_eq!(output, expected);
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_no_marker() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            del_comments: true,
+            no_marker: true,
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        data.save_output(&"//⏬main.rs\n// comment\nfn main() {}\n//⏫main.rs".to_string())
+            .unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert_eq!(output, "fn main() {}");
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_read_dependencies() {
+        let dependencies =
+            read_dependencies(Path::new(r"./test/script_dependencies"), "my_lib").unwrap();
+        assert_eq!(
+            dependencies,
+            vec![
+                ("itertools".to_string(), "0.10".to_string()),
+                ("rand".to_string(), "0.8".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_script_shebang() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = Cli {
+            version_header: true,
+            script_shebang: true,
+            ..test_cli(input, "csf_cg_lib_test")
+        };
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        data.save_output(&"fn main() {}".to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let expected = "#!/usr/bin/env rust-script\n//! ```cargo\n//! [dependencies]\n//! ```\n// fused crates: csf_cg_binary_test 0.1.0, csf_cg_lib_test 0.1.0\nfn main() {}";
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert_eq!(output, expected);
+
+        // existing header is replaced, not duplicated
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert_eq!(output, expected);
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_filter_keeps_used_fn_type_alias() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"#[derive(Copy, Clone)]
+struct MapPoint {
+    x: usize,
+}
+type IsCellFreeFn<T> = Box<dyn Fn(MapPoint, &T) -> bool>;
+type FilterFn<T> = Box<dyn Fn(MapPoint, &T, usize) -> bool>;
+fn is_free(p: MapPoint, v: &u8, f: IsCellFreeFn<u8>) -> bool {
+    f(p, v)
+}
+fn main() {
+    let f: IsCellFreeFn<u8> = Box::new(|p, v| p.x > *v as usize);
+    println!("{}", is_free(MapPoint { x: 1 }, &0, f));
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        // used alias and types referenced by alias survive
+        assert!(output.contains("type IsCellFreeFn<T> = Box<dyn Fn(MapPoint, &T) -> bool>;"));
+        assert!(output.contains("struct MapPoint {"));
+        // unused alias is removed
+        assert!(!output.contains("FilterFn"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_filter_keeps_display_impl_of_formatted_type() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"use std::fmt;
+struct Cell {
+    value: u8,
+}
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.value)
+    }
+}
+struct Unused;
+impl fmt::Display for Unused {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unused")
+    }
+}
+fn main() {
+    let cell = Cell { value: 1 };
+    let line = format!("{cell}");
+    println!("{}", line);
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        // Display impl of type formatted via captured identifier survives
+        assert!(output.contains("impl fmt::Display for Cell {"));
+        assert!(output.contains("write!(f, \"[{}]\", self.value)"));
+        // never constructed type is removed including its Display impl
+        assert!(!output.contains("Unused"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_filter_keeps_impl_used_as_trait_object() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"trait Shape {
+    fn area(&self) -> u32;
+}
+struct Square {
+    side: u32,
+}
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.side * self.side
+    }
+}
+struct Circle;
+impl Shape for Circle {
+    fn area(&self) -> u32 {
+        3
+    }
+}
+fn print_area(shape: &dyn Shape) {
+    println!("{}", shape.area());
+}
+fn main() {
+    let square = Square { side: 2 };
+    print_area(&square);
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        // impl of type only passed as trait object survives
+        assert!(output.contains("impl Shape for Square {"));
+        assert!(output.contains("fn print_area(shape: &dyn Shape) {"));
+        // never constructed type is removed including its impl
+        assert!(!output.contains("Circle"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_filter_keeps_target_type_of_cast() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"type Index = usize;
+type Unused = u64;
+#[derive(Clone, Copy)]
+enum Color {
+    Red = 1,
+    Green = 2,
+}
+fn main() {
+    let color = Color::Green;
+    let index = color as Index;
+    let value = 7_u8 as u32;
+    println!("{} {}", index, value);
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        // alias used as target type of cast survives
+        assert!(output.contains("type Index = usize;"));
+        assert!(output.contains("let index = color as Index;"));
+        // casted enum survives, but never constructed variant is removed
+        assert!(output.contains("Green = 2,"));
+        assert!(!output.contains("Red"));
+        // casts to primitive types do not require any item
+        assert!(output.contains("let value = 7_u8 as u32;"));
+        assert!(!output.contains("Unused"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_filter_keeps_default_impl() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"struct Counter {
+    value: u32,
+}
+impl Default for Counter {
+    fn default() -> Self {
+        Counter { value: 3 }
+    }
+}
+fn unused_counter() -> Counter {
+    Counter { value: 0 }
+}
+fn main() {
+    let counter: Counter = Default::default();
+    println!("{}", counter.value);
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        // Default impl of type constructed via inferred Default::default() survives
+        assert!(output.contains("impl Default for Counter {"));
+        assert!(output.contains("Counter { value: 3 }"));
+        assert!(!output.contains("unused_counter"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_filter_keeps_type_of_associated_type_binding() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"struct Token(u8);
+struct Unused {
+    value: u8,
+}
+fn tokens() -> impl Iterator<Item = Token> {
+    (0..3).map(Token)
+}
+fn print_all<I: Iterator<Item = Token>>(iter: I) {
+    for token in iter {
+        println!("{}", token.0);
+    }
+}
+fn main() {
+    print_all(tokens());
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        // type referenced by associated type bindings survives
+        assert!(output.contains("struct Token(u8);"));
+        assert!(output.contains("fn tokens() -> impl Iterator<Item = Token> {"));
+        assert!(output.contains("fn print_all<I: Iterator<Item = Token>>(iter: I) {"));
+        assert!(!output.contains("Unused"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_filter_keeps_attributes_of_items() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"#[derive(Debug, Clone)]
+#[repr(C)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+#[allow(dead_code)]
+#[derive(Debug)]
+enum Direction {
+    North,
+    South,
+}
+#[derive(Debug, Clone)]
+#[repr(C)]
+struct Unused {
+    value: u8,
+}
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    let copy = point.clone();
+    println!("{:?} {} {:?}", copy, point.x + point.y, Direction::North);
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        // attributes of kept items survive unchanged and in order
+        assert!(output.contains("#[derive(Debug, Clone)]\n#[repr(C)]\nstruct Point {"));
+        assert!(output.contains("#[allow(dead_code)]\n#[derive(Debug)]\nenum Direction {"));
+        assert!(!output.contains("Unused"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_purge_report() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        let report_path = data.tmp_dir.join("purge_report.json");
+        data.options.purge_report = Some(report_path.clone());
+
+        let code = r#"fn unused() {}
+#[derive(Debug)]
+enum Action {
+    Wait,
+    Move,
+}
+fn main() {
+    let count = 3;
+    println!("{:?}", Action::Wait);
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+        let purged = report["purged"].as_array().unwrap();
+        let entry = |action: &str| {
+            purged
+                .iter()
+                .find(|e| e["action"].as_str() == Some(action))
+                .unwrap()
+        };
+        assert_eq!(entry("snip")["line"].as_u64(), Some(1));
+        assert_eq!(entry("snip")["round"].as_u64(), Some(1));
+        assert_eq!(entry("snip")["code"].as_str(), Some("dead_code"));
+        assert_eq!(entry("rename_unused_variable")["line"].as_u64(), Some(8));
+        assert!(entry("rename_unused_variable")["message"]
+            .as_str()
+            .unwrap()
+            .contains("count"));
+        assert_eq!(entry("snip_enum_variant")["line"].as_u64(), Some(5));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_purge_rounds() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"use std::fmt::Write;
+fn unused_write(value: u32) -> String {
+    let mut text = String::new();
+    write!(text, "{}", value).unwrap();
+    text
+}
+fn main() {
+    println!("main");
+}"#;
+        // first round purges unused functions, which makes use statement unused in second round
+        data.options.purge_rounds = Some(1);
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let limited = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert!(!limited.contains("fn unused_write"));
+        assert!(limited.contains("use std::fmt::Write;"));
+
+        data.options.purge_rounds = None;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let unlimited = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert!(!unlimited.contains("use std::fmt::Write;"));
+        assert!(unlimited.len() < limited.len());
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_purge_with_clippy() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+
+        let code = r#"struct Guard;
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+fn main() {
+    let _guard = Guard;
+}"#;
+        // cargo check does not detect empty drop
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert!(output.contains("impl Drop for Guard {"));
+
+        // clippy detects empty drop, which is purged
+        data.options.purge_with = "clippy".to_string();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert!(!output.contains("impl Drop"));
+        assert!(output.contains("let _guard = Guard;"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_post_transform() {
+        struct RenameIdent;
+        impl Fold for RenameIdent {
+            fn fold_ident(&mut self, ident: syn::Ident) -> syn::Ident {
+                if ident == "Guard" {
+                    syn::Ident::new("Sentinel", ident.span())
+                } else {
+                    ident
+                }
+            }
+        }
+
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.add_post_transform(Box::new(RenameIdent));
+
+        let code = r#"struct Guard(u8);
+fn main() {
+    let guard = Guard(1);
+    println!("{}", guard.0);
+}"#;
+        data.save_output(&code.to_string()).unwrap();
+        data.filter_unused_code().unwrap();
+        let output = fs::read_to_string(&data.tmp_output_file).unwrap();
+        assert!(output.contains("struct Sentinel(u8);"));
+        assert!(output.contains("let guard = Sentinel(1);"));
+        assert!(!output.contains("Guard"));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+
+    #[test]
+    fn test_check_size_limit() {
+        let input = PathBuf::from(r"../csf_cg_binary_test/src/main.rs");
+        let options = test_cli(input, "csf_cg_lib_test");
+        let data = CGData::new(options);
+
+        let output = format!("fn main() {{}}\n// {}", "ä".repeat(100_000));
+        let warning = data.check_size_limit(&output).unwrap();
+        assert_eq!(data.fusion_size(), 100_016);
+        assert_eq!(
+            warning,
+            "warning: output has 100016 characters (200016 bytes), which exceeds size limit of 100000 characters"
+        );
+
+        let output = "fn main() {}";
+        assert_eq!(data.check_size_limit(output), None);
+        assert_eq!(data.fusion_size(), 12);
+    }
+
+    #[test]
+    fn test_minify() {
+        let input = PathBuf::from(r"./test/workspace/challenge/src/main.rs");
+        let options = test_cli(input, "shared");
+        let mut data = CGData::new(options);
+        data.prepare_cg_data().unwrap();
+        data.create_output().unwrap();
+        data.filter_unused_code().unwrap();
+        let formatted = fs::read_to_string(&data.tmp_output_file).unwrap();
+        syn::parse_file(&formatted).unwrap();
+
+        data.options.minify = true;
+        data.filter_unused_code().unwrap();
+        let minified = fs::read_to_string(&data.tmp_output_file).unwrap();
+        syn::parse_file(&minified).unwrap();
+        assert!(minified.len() < formatted.len());
+        assert!(!minified.contains("//⏬"));
+        assert!(minified.lines().all(|l| !l.starts_with(' ')));
+
+        // string literals are not changed
+        let code = "fn main() {\n    let s = \"a  b\n    c\";\n    println!(\"{}\", s);\n}";
+        let minified = minify(code, "\n").unwrap();
+        assert!(minified.contains("\"a  b\n    c\""));
+
+        // clean up tmp dir
+        data.cleanup_cg_data().unwrap();
+        assert!(!data.tmp_dir.is_dir());
+    }
+}
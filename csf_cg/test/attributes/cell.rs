@@ -0,0 +1,12 @@
+#[repr(u8)]
+#[derive(Copy, Clone, PartialEq)]
+pub enum Cell {
+    Free = 0,
+    Wall = 1,
+}
+
+#[repr(C)]
+pub struct Position {
+    pub x: u16,
+    pub y: u16,
+}
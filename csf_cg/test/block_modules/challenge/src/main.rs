@@ -0,0 +1,8 @@
+use toolbox::debug::dump;
+use toolbox::geometry::manhattan;
+
+fn main() {
+    let distance = manhattan((0, 0), (3, 4));
+    dump(distance);
+    println!("{}", distance);
+}
@@ -0,0 +1,3 @@
+pub fn dump(value: i32) {
+    eprintln!("debug: {}", value);
+}
@@ -0,0 +1,2 @@
+#[derive(Default)]
+pub struct Counter(pub u32);
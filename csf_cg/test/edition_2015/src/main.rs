@@ -0,0 +1,5 @@
+use legacy::Counter;
+
+fn main() {
+    println!("{}", Counter::default().0);
+}
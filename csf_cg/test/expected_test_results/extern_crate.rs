@@ -0,0 +1,11 @@
+// fused crates: extern_crate 0.1.0, shared 0.2.0
+//⏬lib.rs
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+//⏫lib.rs
+//⏬main.rs
+fn main() {
+    println!("{}", add(1, 2));
+}
+//⏫main.rs
\ No newline at end of file
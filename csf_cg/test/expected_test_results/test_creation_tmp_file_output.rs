@@ -1,7 +1,14 @@
 //⏬my_map_two_dim.rs
 mod my_map_point;
 
+use super::my_array::*;
+use self::my_map_point::*;
 use my_map_point::my_compass::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+type IsCellFreeFn<const X: usize, const Y: usize, T> = Box<dyn Fn(MapPoint<X, Y>, &T) -> bool>;
+type FilterFn<const X: usize, const Y: usize, T> = Box<dyn Fn(MapPoint<X, Y>, &T, usize) -> bool>;
 
 // use MyMap2D if compilation time is suffice, because it is more efficient and has cleaner interface
 #[derive(Copy, Clone, PartialEq)]
@@ -38,7 +45,70 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
         self.items[coordinates.y()][coordinates.x()] = value;
         &self.items[coordinates.y()][coordinates.x()]
     }
-    fn is_cut_off_cell(&self, map_point: MapPoint<X, Y>, is_cell_free_fn: Box<dyn Fn(MapPoint<X, Y>, &T) -> bool>) -> bool {
+    fn get_checked(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= X || y >= Y {
+            return None;
+        }
+        Some(&self.items[y][x])
+    }
+    fn set_checked(&mut self, x: usize, y: usize, value: T) -> Option<&T> {
+        if x >= X || y >= Y {
+            return None;
+        }
+        self.items[y][x] = value;
+        Some(&self.items[y][x])
+    }
+    fn map<U: Copy + Clone + Default, F: Fn(MapPoint<X, Y>, &T) -> U>(&self, f: F) -> MyMap2D<U, X, Y, N> {
+        let mut result: MyMap2D<U, X, Y, N> = MyMap2D::new();
+        for (p, v) in self.iter() {
+            result.set(p, f(p, v));
+        }
+        result
+    }
+    fn step<A: Fn(&T) -> bool, F: Fn(MapPoint<X, Y>, &T, usize) -> T>(&self, include_corners: bool, is_alive: A, transition: F) -> Self {
+        // next generation is written to new map; transition gets number of alive neighbors of each cell in current map
+        self.map(|p, v| transition(p, v, self.neighbors_count_where(p, include_corners, &is_alive)))
+    }
+    fn count_where<F: Fn(MapPoint<X, Y>, &T) -> bool>(&self, f: F) -> usize {
+        self.iter().filter(|(p, v)| f(*p, v)).count()
+    }
+    fn any_where<F: Fn(MapPoint<X, Y>, &T) -> bool>(&self, f: F) -> bool {
+        self.iter().any(|(p, v)| f(p, v))
+    }
+    fn transpose(&self) -> MyMap2D<T, Y, X, N> {
+        let mut result: MyMap2D<T, Y, X, N> = MyMap2D::new();
+        for (y, row) in self.items.iter().enumerate() {
+            for (x, item) in row.iter().enumerate() {
+                result.items[x][y] = *item;
+            }
+        }
+        result
+    }
+    fn rotate_90_cw(&self) -> Self {
+        if X != Y {
+            panic!("line {}, rotation requires square map", line!());
+        }
+        let mut result = *self;
+        for (y, row) in result.items.iter_mut().enumerate() {
+            for (x, item) in row.iter_mut().enumerate() {
+                *item = self.items[X - 1 - x][y];
+            }
+        }
+        result
+    }
+    fn rotate_90_ccw(&self) -> Self {
+        if X != Y {
+            panic!("line {}, rotation requires square map", line!());
+        }
+        let mut result = *self;
+        for (y, row) in result.items.iter_mut().enumerate() {
+            for (x, item) in row.iter_mut().enumerate() {
+                *item = self.items[x][Y - 1 - y];
+            }
+        }
+        result
+    }
+    fn is_cut_off_cell(&self, map_point: MapPoint<X, Y>, is_cell_free_fn: IsCellFreeFn<X, Y, T>) -> bool {
         // use is_cell_free_fn as follows (use "_" for unused variables):
         // let is_cell_free_fn = Box::new(|current_point: MapPoint<X, Y>, current_cell_value: &T| current_point.use_it_somehow() || current_cell_value.use_it_somehow() );
         let (mut last_free, initial_orientation) = match map_point.map_position() {
@@ -100,12 +170,36 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
             .enumerate()
             .flat_map(move |(y, row)| row.iter().enumerate().filter(move |(x, _)| *x == c).map(move |(x, column)| (MapPoint::new(x, y), column)))
     }
+    fn iter_row_mut(&mut self, r: usize) -> impl Iterator<Item = (MapPoint<X, Y>, &mut T)> {
+        if r >= Y {
+            panic!("line {}, row index is out of range", line!());
+        }
+        self.items[r]
+            .iter_mut()
+            .enumerate()
+            .map(move |(x, column)| (MapPoint::new(x, r), column))
+    }
+    fn iter_column_mut(&mut self, c: usize) -> impl Iterator<Item = (MapPoint<X, Y>, &mut T)> {
+        if c >= X {
+            panic!("line {}, column index is out of range", line!());
+        }
+        self.items
+            .iter_mut()
+            .enumerate()
+            .map(move |(y, row)| (MapPoint::new(c, y), &mut row[c]))
+    }
     fn iter_neighbors(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
         center_point.iter_neighbors(Compass::N, true, false, false).map(move |(p, _)| (p, self.get(p)))
     }
+    fn neighbor_values(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = &T> {
+        self.iter_neighbors(center_point).map(|(_, v)| v)
+    }
     fn iter_neighbors_mut(&mut self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &mut T)> {
         center_point.iter_neighbors(Compass::N, true, false, false).map(move |(p, _)| unsafe { (p, &mut *(self.get_mut(p) as *mut _ )) } )
     }
+    fn iter_neighbors_wrapping(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
+        center_point.iter_neighbors_wrapping(Compass::N, true, false, false).map(move |(p, _)| (p, self.get(p)))
+    }
     fn iter_neighbors_with_center(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
         center_point.iter_neighbors(Compass::N, true, true, false).map(move |(p, _)| (p, self.get(p)))
     }
@@ -115,6 +209,13 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
     fn iter_neighbors_with_center_and_corners(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &T, bool)> {
         center_point.iter_neighbors(Compass::N, true, true, true).map(move |(p, o)| (p, self.get(p), o.is_ordinal()))
     }
+    fn neighbors_count_where<F: Fn(&T) -> bool>(&self, center_point: MapPoint<X, Y>, include_corners: bool, f: F) -> usize {
+        if include_corners {
+            self.iter_neighbors_with_corners(center_point).filter(|(_, v, _)| f(v)).count()
+        } else {
+            self.iter_neighbors(center_point).filter(|(_, v)| f(v)).count()
+        }
+    }
     fn iter_orientation(&self, start_point: MapPoint<X, Y>, orientation: Compass) -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
         start_point.iter_orientation(orientation).map(move |p| (p, self.get(p)))
     }
@@ -130,11 +231,86 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
     fn iter_diagonale_bottom_right(&self)  -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
         MapPoint::<X, Y>::new(X - 1, Y - 1).iter_orientation(Compass::NW).map(move |p| (p, self.get(p)))
     }
-    fn iter_distance<'a>(&'a self, start_point: MapPoint<X, Y>, filter_fn: Box<dyn Fn(MapPoint<X, Y>, &T, usize) -> bool>) -> impl Iterator<Item = (MapPoint<X, Y>, &'a T, usize)> {
+    fn iter_distance<'a>(&'a self, start_point: MapPoint<X, Y>, filter_fn: FilterFn<X, Y, T>) -> impl Iterator<Item = (MapPoint<X, Y>, &'a T, usize)> {
         // use filter_fn as follows (use "_" for unused variables):
         // let filter_fn = Box::new(|point_of_next_cell: MapPoint<X, Y>, value_of_next_cell: &T, current_distance: usize| current_point.use_it_somehow() || current_cell_value.use_it_somehow() || current_distance.use_it_somehow());
         DistanceIter::new(self, start_point, filter_fn)
     }
+    fn iter_weighted_distance<F: Fn(MapPoint<X, Y>, &T) -> Option<usize>>(&self, start_point: MapPoint<X, Y>, cost_fn: F) -> impl Iterator<Item = (MapPoint<X, Y>, &T, usize)> {
+        // cost_fn returns cost to enter cell or None, if cell is blocked; cells are returned with nondecreasing accumulated cost
+        WeightedDistanceIter::new(self, start_point, cost_fn)
+    }
+    fn distance_field(&self, sources: &[MapPoint<X, Y>], filter_fn: FilterFn<X, Y, T>) -> MyMap2D<usize, X, Y, N> {
+        // multi source breadth first search; filter_fn is used like in iter_distance; unreachable cells keep usize::MAX
+        let mut distances: MyMap2D<usize, X, Y, N> = MyMap2D::init(usize::MAX);
+        let mut next_cells: MyArray<MapPoint<X, Y>, N> = MyArray::new();
+        for source in sources.iter() {
+            if *distances.get(*source) == usize::MAX {
+                distances.set(*source, 0);
+                next_cells.push(*source);
+            }
+        }
+        let mut index = 0;
+        while let Some(current_point) = next_cells.get(index).copied() {
+            let distance = *distances.get(current_point);
+            for (next_cell, value) in self.iter_neighbors(current_point) {
+                if *distances.get(next_cell) == usize::MAX && filter_fn(next_cell, value, distance) {
+                    distances.set(next_cell, distance + 1);
+                    next_cells.push(next_cell);
+                }
+            }
+            index += 1;
+        }
+        distances
+    }
+    fn flood_fill<F: Fn(&T) -> bool>(&self, seed: MapPoint<X, Y>, predicate: F) -> Vec<MapPoint<X, Y>> {
+        // 4-connected region of cells satisfying predicate, which includes seed; empty if seed does not satisfy predicate
+        if !predicate(self.get(seed)) {
+            return Vec::new();
+        }
+        let mut visited: MyMap2D<bool, X, Y, N> = MyMap2D::new();
+        let mut region = vec![seed];
+        visited.set(seed, true);
+        let mut index = 0;
+        while let Some(current_point) = region.get(index).copied() {
+            for (next_cell, value) in self.iter_neighbors(current_point) {
+                if !*visited.get(next_cell) && predicate(value) {
+                    visited.set(next_cell, true);
+                    region.push(next_cell);
+                }
+            }
+            index += 1;
+        }
+        region
+    }
+    fn shortest_path(&self, start: MapPoint<X, Y>, target: MapPoint<X, Y>, is_cell_free_fn: IsCellFreeFn<X, Y, T>) -> Option<Vec<MapPoint<X, Y>>> {
+        // breadth first search, which records predecessor of each visited cell; start is its own predecessor
+        let mut predecessors: MyMap2D<Option<MapPoint<X, Y>>, X, Y, N> = MyMap2D::new();
+        let mut next_cells: MyArray<MapPoint<X, Y>, N> = MyArray::init(start, 1);
+        predecessors.set(start, Some(start));
+        let mut index = 0;
+        while let Some(current_point) = next_cells.get(index).copied() {
+            if current_point == target {
+                // walk back from target to start
+                let mut path = vec![target];
+                let mut path_point = target;
+                while path_point != start {
+                    path_point = predecessors.get(path_point).unwrap();
+                    path.push(path_point);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (next_cell, _) in current_point.iter_neighbors(Compass::N, true, false, false) {
+                if predecessors.get(next_cell).is_none() && is_cell_free_fn(next_cell, self.get(next_cell)) {
+                    predecessors.set(next_cell, Some(current_point));
+                    next_cells.push(next_cell);
+                }
+            }
+            index += 1;
+        }
+        None
+    }
 }
 
 impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize> Default for MyMap2D<T, X, Y, N> {
@@ -143,16 +319,31 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
     }
 }
 
+impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize> FromIterator<(MapPoint<X, Y>, T)> for MyMap2D<T, X, Y, N> {
+    fn from_iter<I: IntoIterator<Item = (MapPoint<X, Y>, T)>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for (p, v) in iter {
+            result.set(p, v);
+        }
+        result
+    }
+}
+
+// serde array impls stop at length 32, therefore map is serialized as flat sequence of X * Y items (row by row)
+
+
+
+
 
 struct DistanceIter<'a, T, const X: usize, const Y: usize, const N: usize> {
     data_map: &'a MyMap2D<T, X, Y, N>,
-    filter_fn: Box<dyn Fn(MapPoint<X, Y>, &T, usize) -> bool>, // input for filter_fn: next possible point, data from data_map of next possible point, distance of current point
+    filter_fn: FilterFn<X, Y, T>, // input for filter_fn: next possible point, data from data_map of next possible point, distance of current point
     next_cells: MyArray<(MapPoint<X, Y>, usize), N>,
     index: usize,
 }
 
 impl<'a, T: Copy + Clone, const X: usize, const Y: usize, const N: usize> DistanceIter<'a, T, X, Y, N> {
-    fn new(data_map: &'a MyMap2D<T, X, Y, N>, start_point: MapPoint<X, Y>, filter_fn: Box<dyn Fn(MapPoint<X, Y>, &T, usize) -> bool>) -> Self {
+    fn new(data_map: &'a MyMap2D<T, X, Y, N>, start_point: MapPoint<X, Y>, filter_fn: FilterFn<X, Y, T>) -> Self {
         DistanceIter {
             data_map,
             filter_fn,
@@ -179,11 +370,60 @@ impl<'a, T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usi
         Some((map_point, self.data_map.get(map_point), distance))
     }
 }
+
+struct WeightedDistanceIter<'a, T, F, const X: usize, const Y: usize, const N: usize> {
+    data_map: &'a MyMap2D<T, X, Y, N>,
+    cost_fn: F,
+    costs: MyMap2D<usize, X, Y, N>, // lowest known accumulated cost of each cell
+    visited: MyMap2D<bool, X, Y, N>,
+    next_cells: BinaryHeap<Reverse<(usize, usize, usize)>>, // accumulated cost, x, y
+}
+
+impl<'a, T: Copy + Clone + Default, F: Fn(MapPoint<X, Y>, &T) -> Option<usize>, const X: usize, const Y: usize, const N: usize> WeightedDistanceIter<'a, T, F, X, Y, N> {
+    fn new(data_map: &'a MyMap2D<T, X, Y, N>, start_point: MapPoint<X, Y>, cost_fn: F) -> Self {
+        let mut costs: MyMap2D<usize, X, Y, N> = MyMap2D::init(usize::MAX);
+        costs.set(start_point, 0);
+        WeightedDistanceIter {
+            data_map,
+            cost_fn,
+            costs,
+            visited: MyMap2D::new(),
+            next_cells: BinaryHeap::from([Reverse((0, start_point.x(), start_point.y()))]),
+        }
+    }
+}
+
+impl<'a, T: Copy + Clone + Default, F: Fn(MapPoint<X, Y>, &T) -> Option<usize>, const X: usize, const Y: usize, const N: usize> Iterator for WeightedDistanceIter<'a, T, F, X, Y, N> {
+    type Item = (MapPoint<X, Y>, &'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(Reverse((cost, x, y))) = self.next_cells.pop() {
+            let map_point = MapPoint::<X, Y>::new(x, y);
+            // cells may be queued multiple times; only first pop has lowest cost
+            if *self.visited.get(map_point) {
+                continue;
+            }
+            self.visited.set(map_point, true);
+            for (next_cell, value) in self.data_map.iter_neighbors(map_point) {
+                if let Some(step_cost) = (self.cost_fn)(next_cell, value) {
+                    let next_cost = cost + step_cost;
+                    if next_cost < *self.costs.get(next_cell) {
+                        self.costs.set(next_cell, next_cost);
+                        self.next_cells.push(Reverse((next_cost, next_cell.x(), next_cell.y())));
+                    }
+                }
+            }
+            return Some((map_point, self.data_map.get(map_point), cost));
+        }
+        None
+    }
+}
 //⏫my_map_two_dim.rs
 //⏬my_map_point.rs
 mod my_compass;
 
 use std::cmp::Ordering;
+use crate::my_map_two_dim::my_map_point::my_compass::*;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
 struct MapPoint<const X: usize, const Y: usize> {
@@ -231,6 +471,12 @@ impl<const X: usize, const Y: usize> MapPoint<X, Y> {
     fn distance(&self, target: MapPoint<X, Y>) -> usize {
         self.distance_x(target) + self.distance_y(target)
     }
+    fn manhattan_distance(&self, other: MapPoint<X, Y>) -> usize {
+        self.distance(other)
+    }
+    fn chebyshev_distance(&self, other: MapPoint<X, Y>) -> usize {
+        self.distance_x(other).max(self.distance_y(other))
+    }
     fn delta_xy(&self, target: MapPoint<X, Y>) -> usize {
         let dist_x = self.distance_x(target);
         let dist_y = self.distance_y(target);
@@ -335,6 +581,22 @@ impl<const X: usize, const Y: usize> MapPoint<X, Y> {
             None
         }
     }
+    fn offset_checked(&self, dx: isize, dy: isize) -> Option<MapPoint<X, Y>> {
+        let x = self.x.checked_add_signed(dx)?;
+        let y = self.y.checked_add_signed(dy)?;
+        let result = MapPoint { x, y };
+        if result.is_in_map() {
+            Some(result)
+        } else {
+            None
+        }
+    }
+    fn offset_saturating(&self, dx: isize, dy: isize) -> MapPoint<X, Y> {
+        MapPoint {
+            x: self.x.saturating_add_signed(dx).min(X - 1),
+            y: self.y.saturating_add_signed(dy).min(Y - 1),
+        }
+    }
     fn neighbor(&self, orientation: Compass) -> Option<MapPoint<X, Y>> {
         match orientation {
             Compass::Center => Some(*self),
@@ -348,15 +610,40 @@ impl<const X: usize, const Y: usize> MapPoint<X, Y> {
             Compass::NW => self.offset_mm((1, 1)),
         }
     }
+    fn neighbor_wrapping(&self, orientation: Compass) -> MapPoint<X, Y> {
+        // map is handled as torus: leaving map at one side results in entering map at opposite side
+        let west = (self.x + X - 1) % X;
+        let east = (self.x + 1) % X;
+        let north = (self.y + Y - 1) % Y;
+        let south = (self.y + 1) % Y;
+        let (x, y) = match orientation {
+            Compass::Center => (self.x, self.y),
+            Compass::N => (self.x, north),
+            Compass::NE => (east, north),
+            Compass::E => (east, self.y),
+            Compass::SE => (east, south),
+            Compass::S => (self.x, south),
+            Compass::SW => (west, south),
+            Compass::W => (west, self.y),
+            Compass::NW => (west, north),
+        };
+        MapPoint { x, y }
+    }
     fn orientation_of_neighbor(&self, neighbor: MapPoint<X, Y>) -> Option<Compass> {
         self.iter_neighbors(Compass::N, true, false, true).find(|(p, _)| *p == neighbor).map_or(None, |(_, o)| Some(o))
     }
     fn iter_neighbors(&self, initial_orientation: Compass, rotation_direction: bool, include_center: bool, include_corners: bool) -> impl Iterator<Item = (MapPoint<X, Y>, Compass)> {
-        NeighborIter::new(*self, initial_orientation, rotation_direction, include_center, include_corners)
+        NeighborIter::new(*self, initial_orientation, rotation_direction, include_center, include_corners, false)
+    }
+    fn iter_neighbors_wrapping(&self, initial_orientation: Compass, rotation_direction: bool, include_center: bool, include_corners: bool) -> impl Iterator<Item = (MapPoint<X, Y>, Compass)> {
+        NeighborIter::new(*self, initial_orientation, rotation_direction, include_center, include_corners, true)
     }
     fn iter_orientation(&self, orientation: Compass) -> impl Iterator<Item = MapPoint<X, Y>> {
         OrientationIter::new(*self, orientation)
     }
+    fn iter_line_to(&self, target: MapPoint<X, Y>) -> impl Iterator<Item = MapPoint<X, Y>> {
+        LineIter::new(*self, target)
+    }
 }
 
 struct NeighborIter<const X: usize, const Y: usize> {
@@ -366,11 +653,12 @@ struct NeighborIter<const X: usize, const Y: usize> {
     initial_orientation: Compass,
     current_orientation: Compass,
     rotation_direction: bool,
+    wrapping: bool,
     finished: bool,
 }
 
 impl<const X: usize, const Y: usize>NeighborIter<X, Y> {
-    fn new(center_point: MapPoint<X, Y>, initial_orientation: Compass, rotation_direction: bool, include_center: bool, include_corners: bool) -> Self {
+    fn new(center_point: MapPoint<X, Y>, initial_orientation: Compass, rotation_direction: bool, include_center: bool, include_corners: bool, wrapping: bool) -> Self {
         if initial_orientation.is_center() {
             panic!("line {}, need direction", line!());
         }
@@ -384,6 +672,7 @@ impl<const X: usize, const Y: usize>NeighborIter<X, Y> {
             initial_orientation,
             current_orientation: initial_orientation,
             rotation_direction,
+            wrapping,
             finished: false,
         }
     }
@@ -417,6 +706,8 @@ impl<const X: usize, const Y: usize> Iterator for NeighborIter<X, Y> {
         while !self.finished {
             let result = if self.include_center {
                 Some((self.center_point, Compass::Center))
+            } else if self.wrapping {
+                Some((self.center_point.neighbor_wrapping(self.current_orientation), self.current_orientation))
             } else {
                 self.center_point.neighbor(self.current_orientation).map_or(None, |n| Some((n, self.current_orientation)))
             };
@@ -466,11 +757,64 @@ impl<const X: usize, const Y: usize> Iterator for OrientationIter<X, Y> {
         Some(result)
     }
 }
+
+struct LineIter<const X: usize, const Y: usize> {
+    // Bresenham line from start point to target, both included
+    current: (isize, isize),
+    target: (isize, isize),
+    delta: (isize, isize), // dx >= 0, dy <= 0
+    step: (isize, isize),
+    error: isize,
+    finished: bool,
+}
+
+impl <const X: usize, const Y: usize>LineIter<X, Y> {
+    fn new(start_point: MapPoint<X, Y>, target: MapPoint<X, Y>) -> Self {
+        let current = (start_point.x() as isize, start_point.y() as isize);
+        let target = (target.x() as isize, target.y() as isize);
+        let delta = ((target.0 - current.0).abs(), -(target.1 - current.1).abs());
+        LineIter {
+            current,
+            target,
+            delta,
+            step: ((target.0 - current.0).signum(), (target.1 - current.1).signum()),
+            error: delta.0 + delta.1,
+            finished: false,
+        }
+    }
+}
+
+impl<const X: usize, const Y: usize> Iterator for LineIter<X, Y> {
+    type Item = MapPoint<X, Y>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None
+        }
+        let result = MapPoint::<X, Y>::new(self.current.0 as usize, self.current.1 as usize);
+        if self.current == self.target {
+            self.finished = true;
+            return Some(result);
+        }
+        let double_error = 2 * self.error;
+        if double_error >= self.delta.1 {
+            self.error += self.delta.1;
+            self.current.0 += self.step.0;
+        }
+        if double_error <= self.delta.0 {
+            self.error += self.delta.0;
+            self.current.1 += self.step.1;
+        }
+        Some(result)
+    }
+}
 //⏫my_map_point.rs
 //⏬lib.rs
 // lib.rs - sample lib file for local crate
 mod action;
 
+use crate::action::*;
+use csf_cg_lib_test::my_map_two_dim::*;
 
 use std::fmt;
 
@@ -518,6 +862,7 @@ impl Go {
 //⏫lib.rs
 //⏬action.rs
 use super::*;
+use csf_cg_lib_test::my_map_two_dim::my_map_point::*;
 
 struct Action {
     cell: MapPoint<X, Y>,
@@ -541,6 +886,9 @@ impl Action {
 //⏫action.rs
 //⏬main.rs
 // main.rs - main test input file
+use csf_cg_binary_test::{Go, X, Y};
+use csf_cg_binary_test::action::Action;
+use csf_cg_lib_test::my_map_two_dim::my_map_point::*;
 
 
 
@@ -0,0 +1,11 @@
+pub fn double(value: u32) -> u32 {
+    value * 2
+}
+#[cfg(feature = "triple")]
+pub fn triple(value: u32) -> u32 {
+    value * 3
+}
+#[cfg(feature = "quad")]
+pub fn quad(value: u32) -> u32 {
+    value * 4
+}
@@ -0,0 +1,3 @@
+fn main() {
+    println!("{} {}", double(21), triple(14));
+}
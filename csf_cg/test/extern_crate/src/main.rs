@@ -0,0 +1,7 @@
+extern crate shared as common;
+
+use common::add;
+
+fn main() {
+    println!("{}", add(1, 2));
+}
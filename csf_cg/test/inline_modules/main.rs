@@ -0,0 +1,14 @@
+// main.rs - inline module with file based sub modules
+mod outer {
+    pub mod inner;
+
+    pub mod inline_only {
+        pub fn answer() -> usize {
+            42
+        }
+    }
+}
+
+fn main() {
+    println!("{}", outer::inner::deep::value() + outer::inline_only::answer());
+}
@@ -0,0 +1,3 @@
+pub fn value() -> usize {
+    1
+}
@@ -0,0 +1,16 @@
+// helper types, which are used by examples and benchmarks of challenge
+pub mod tests {
+    pub struct Sample {
+        pub value: u8,
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::tests::*;
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(Sample { value: 1 }.value, 1);
+    }
+}
@@ -0,0 +1,7 @@
+fn greeting() -> &'static str {
+    "second"
+}
+
+fn main() {
+    println!("{}", greeting());
+}
@@ -0,0 +1,2 @@
+#[derive(Default)]
+pub struct Flags(pub u8);
@@ -0,0 +1,5 @@
+use shared::Flags;
+
+fn main() {
+    println!("{}", Flags::default().0);
+}
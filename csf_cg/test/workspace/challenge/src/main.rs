@@ -0,0 +1,5 @@
+use shared::add;
+
+fn main() {
+    println!("{}", add(20, 22));
+}
@@ -26,6 +26,14 @@ impl<T: Copy + Clone + Default, const N: usize> MyArray<T, N> {
         self.items[self.n_items] = item;
         self.n_items += 1;
     }
+    pub fn try_push(&mut self, item: T) -> Result<(), T> {
+        if self.n_items == N {
+            return Err(item);
+        }
+        self.items[self.n_items] = item;
+        self.n_items += 1;
+        Ok(())
+    }
     pub fn pop(&mut self) -> Option<T> {
         if self.n_items == 0 {
             return None;
@@ -58,6 +66,25 @@ impl<T: Copy + Clone + Default, const N: usize> MyArray<T, N> {
         self.n_items -= 1;
         Some(result)
     }
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        if index >= self.n_items {
+            panic!("line {}, index {} out of range", line!(), index);
+        }
+        let result = self.items[index];
+        self.n_items -= 1;
+        self.items[index] = self.items[self.n_items];
+        result
+    }
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut n_retained = 0;
+        for index in 0..self.n_items {
+            if f(&self.items[index]) {
+                self.items[n_retained] = self.items[index];
+                n_retained += 1;
+            }
+        }
+        self.n_items = n_retained;
+    }
     pub fn flush(&mut self) {
         self.n_items = 0;
     }
@@ -91,9 +118,13 @@ impl<T: Copy + Clone + Default, const N: usize> MyArray<T, N> {
     pub fn as_slice(&self) -> &[T] {
         &self.items[..self.n_items]
     }
-    pub fn as_slice_mut(&mut self) -> &mut [T] {
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
         &mut self.items[..self.n_items]
     }
+    #[deprecated(note = "use as_mut_slice()")]
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
     pub fn append_slice(&mut self, slice: &[T]) {
         if self.n_items + slice.len() > N {
             panic!("line {}", line!());
@@ -103,6 +134,15 @@ impl<T: Copy + Clone + Default, const N: usize> MyArray<T, N> {
         }
         self.n_items += slice.len();
     }
+    pub fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            if self.n_items == N {
+                panic!("line {}, capacity of {} items exceeded", line!(), N);
+            }
+            self.items[self.n_items] = item;
+            self.n_items += 1;
+        }
+    }
     pub fn set(&mut self, index: usize, item: T) -> Option<&T> {
         if index >= self.n_items {
             return None;
@@ -167,4 +207,75 @@ mod tests {
         let array = [0, 1, 2, 3, 4];
         let _my_array: MyArray<i32, 4> = array.iter().map(|i| *i).collect();
     }
+
+    #[test]
+    fn test_extend() {
+        let mut my_array: MyArray<usize, 10> = MyArray::new();
+        my_array.push(10);
+        my_array.extend(0..5);
+        assert_eq!(my_array.as_slice(), &[10, 0, 1, 2, 3, 4][..]);
+        my_array.extend(5..9);
+        assert_eq!(my_array.len(), 10);
+        assert_eq!(my_array.remaining_len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity of 4 items exceeded")]
+    fn test_extend_beyond_capacity() {
+        let mut my_array: MyArray<usize, 4> = MyArray::new();
+        my_array.extend(0..5);
+    }
+
+    #[test]
+    fn test_try_push() {
+        let mut my_array: MyArray<usize, 4> = MyArray::new();
+        for i in 0..4 {
+            assert_eq!(my_array.try_push(i), Ok(()));
+        }
+        assert_eq!(my_array.try_push(4), Err(4));
+        assert_eq!(my_array.len(), 4);
+        assert_eq!(my_array.as_slice(), &[0, 1, 2, 3][..]);
+        my_array.pop();
+        assert_eq!(my_array.try_push(5), Ok(()));
+        assert_eq!(my_array.as_slice(), &[0, 1, 2, 5][..]);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut my_array: MyArray<usize, 10> = (0..5).collect();
+        my_array.retain(|i| i % 2 == 0);
+        assert_eq!(my_array.len(), 3);
+        assert_eq!(my_array.as_slice(), &[0, 2, 4][..]);
+        my_array.retain(|_| false);
+        assert_eq!(my_array.len(), 0);
+    }
+
+    #[test]
+    fn test_swap_remove() {
+        let mut my_array: MyArray<usize, 10> = (0..5).collect();
+        assert_eq!(my_array.swap_remove(1), 1);
+        assert_eq!(my_array.as_slice(), &[0, 4, 2, 3][..]);
+        assert_eq!(my_array.swap_remove(3), 3);
+        assert_eq!(my_array.as_slice(), &[0, 4, 2][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of range")]
+    fn test_swap_remove_out_of_range() {
+        let mut my_array: MyArray<usize, 10> = (0..3).collect();
+        my_array.swap_remove(3);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut my_array: MyArray<usize, 10> = (1..6).collect();
+        for item in my_array.iter_mut() {
+            *item *= 2;
+        }
+        assert_eq!(my_array.as_slice(), &[2, 4, 6, 8, 10][..]);
+        assert_eq!(my_array.as_mut_slice().len(), 5);
+        my_array.as_mut_slice()[0] = 1;
+        assert_eq!(my_array.get(0), Some(&1));
+        assert_eq!(my_array.remaining_len(), 5);
+    }
 }
\ No newline at end of file
@@ -3,6 +3,15 @@ pub mod my_map_point;
 use super::my_array::*;
 use self::my_map_point::*;
 use my_map_point::my_compass::*;
+#[cfg(feature = "serde")]
+use serde::{de, ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use std::marker::PhantomData;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub type IsCellFreeFn<const X: usize, const Y: usize, T> = Box<dyn Fn(MapPoint<X, Y>, &T) -> bool>;
+pub type FilterFn<const X: usize, const Y: usize, T> = Box<dyn Fn(MapPoint<X, Y>, &T, usize) -> bool>;
 
 // use MyMap2D if compilation time is suffice, because it is more efficient and has cleaner interface
 #[derive(Copy, Clone, PartialEq)]
@@ -39,7 +48,70 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
         self.items[coordinates.y()][coordinates.x()] = value;
         &self.items[coordinates.y()][coordinates.x()]
     }
-    pub fn is_cut_off_cell(&self, map_point: MapPoint<X, Y>, is_cell_free_fn: Box<dyn Fn(MapPoint<X, Y>, &T) -> bool>) -> bool {
+    pub fn get_checked(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= X || y >= Y {
+            return None;
+        }
+        Some(&self.items[y][x])
+    }
+    pub fn set_checked(&mut self, x: usize, y: usize, value: T) -> Option<&T> {
+        if x >= X || y >= Y {
+            return None;
+        }
+        self.items[y][x] = value;
+        Some(&self.items[y][x])
+    }
+    pub fn map<U: Copy + Clone + Default, F: Fn(MapPoint<X, Y>, &T) -> U>(&self, f: F) -> MyMap2D<U, X, Y, N> {
+        let mut result: MyMap2D<U, X, Y, N> = MyMap2D::new();
+        for (p, v) in self.iter() {
+            result.set(p, f(p, v));
+        }
+        result
+    }
+    pub fn step<A: Fn(&T) -> bool, F: Fn(MapPoint<X, Y>, &T, usize) -> T>(&self, include_corners: bool, is_alive: A, transition: F) -> Self {
+        // next generation is written to new map; transition gets number of alive neighbors of each cell in current map
+        self.map(|p, v| transition(p, v, self.neighbors_count_where(p, include_corners, &is_alive)))
+    }
+    pub fn count_where<F: Fn(MapPoint<X, Y>, &T) -> bool>(&self, f: F) -> usize {
+        self.iter().filter(|(p, v)| f(*p, v)).count()
+    }
+    pub fn any_where<F: Fn(MapPoint<X, Y>, &T) -> bool>(&self, f: F) -> bool {
+        self.iter().any(|(p, v)| f(p, v))
+    }
+    pub fn transpose(&self) -> MyMap2D<T, Y, X, N> {
+        let mut result: MyMap2D<T, Y, X, N> = MyMap2D::new();
+        for (y, row) in self.items.iter().enumerate() {
+            for (x, item) in row.iter().enumerate() {
+                result.items[x][y] = *item;
+            }
+        }
+        result
+    }
+    pub fn rotate_90_cw(&self) -> Self {
+        if X != Y {
+            panic!("line {}, rotation requires square map", line!());
+        }
+        let mut result = *self;
+        for (y, row) in result.items.iter_mut().enumerate() {
+            for (x, item) in row.iter_mut().enumerate() {
+                *item = self.items[X - 1 - x][y];
+            }
+        }
+        result
+    }
+    pub fn rotate_90_ccw(&self) -> Self {
+        if X != Y {
+            panic!("line {}, rotation requires square map", line!());
+        }
+        let mut result = *self;
+        for (y, row) in result.items.iter_mut().enumerate() {
+            for (x, item) in row.iter_mut().enumerate() {
+                *item = self.items[x][Y - 1 - y];
+            }
+        }
+        result
+    }
+    pub fn is_cut_off_cell(&self, map_point: MapPoint<X, Y>, is_cell_free_fn: IsCellFreeFn<X, Y, T>) -> bool {
         // use is_cell_free_fn as follows (use "_" for unused variables):
         // let is_cell_free_fn = Box::new(|current_point: MapPoint<X, Y>, current_cell_value: &T| current_point.use_it_somehow() || current_cell_value.use_it_somehow() );
         let (mut last_free, initial_orientation) = match map_point.map_position() {
@@ -101,12 +173,36 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
             .enumerate()
             .flat_map(move |(y, row)| row.iter().enumerate().filter(move |(x, _)| *x == c).map(move |(x, column)| (MapPoint::new(x, y), column)))
     }
+    pub fn iter_row_mut(&mut self, r: usize) -> impl Iterator<Item = (MapPoint<X, Y>, &mut T)> {
+        if r >= Y {
+            panic!("line {}, row index is out of range", line!());
+        }
+        self.items[r]
+            .iter_mut()
+            .enumerate()
+            .map(move |(x, column)| (MapPoint::new(x, r), column))
+    }
+    pub fn iter_column_mut(&mut self, c: usize) -> impl Iterator<Item = (MapPoint<X, Y>, &mut T)> {
+        if c >= X {
+            panic!("line {}, column index is out of range", line!());
+        }
+        self.items
+            .iter_mut()
+            .enumerate()
+            .map(move |(y, row)| (MapPoint::new(c, y), &mut row[c]))
+    }
     pub fn iter_neighbors(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
         center_point.iter_neighbors(Compass::N, true, false, false).map(move |(p, _)| (p, self.get(p)))
     }
+    pub fn neighbor_values(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = &T> {
+        self.iter_neighbors(center_point).map(|(_, v)| v)
+    }
     pub fn iter_neighbors_mut(&mut self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &mut T)> {
         center_point.iter_neighbors(Compass::N, true, false, false).map(move |(p, _)| unsafe { (p, &mut *(self.get_mut(p) as *mut _ )) } )
     }
+    pub fn iter_neighbors_wrapping(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
+        center_point.iter_neighbors_wrapping(Compass::N, true, false, false).map(move |(p, _)| (p, self.get(p)))
+    }
     pub fn iter_neighbors_with_center(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
         center_point.iter_neighbors(Compass::N, true, true, false).map(move |(p, _)| (p, self.get(p)))
     }
@@ -116,6 +212,13 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
     pub fn iter_neighbors_with_center_and_corners(&self, center_point: MapPoint<X, Y>) -> impl Iterator<Item = (MapPoint<X, Y>, &T, bool)> {
         center_point.iter_neighbors(Compass::N, true, true, true).map(move |(p, o)| (p, self.get(p), o.is_ordinal()))
     }
+    pub fn neighbors_count_where<F: Fn(&T) -> bool>(&self, center_point: MapPoint<X, Y>, include_corners: bool, f: F) -> usize {
+        if include_corners {
+            self.iter_neighbors_with_corners(center_point).filter(|(_, v, _)| f(v)).count()
+        } else {
+            self.iter_neighbors(center_point).filter(|(_, v)| f(v)).count()
+        }
+    }
     pub fn iter_orientation(&self, start_point: MapPoint<X, Y>, orientation: Compass) -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
         start_point.iter_orientation(orientation).map(move |p| (p, self.get(p)))
     }
@@ -131,11 +234,86 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
     pub fn iter_diagonale_bottom_right(&self)  -> impl Iterator<Item = (MapPoint<X, Y>, &T)> {
         MapPoint::<X, Y>::new(X - 1, Y - 1).iter_orientation(Compass::NW).map(move |p| (p, self.get(p)))
     }
-    pub fn iter_distance<'a>(&'a self, start_point: MapPoint<X, Y>, filter_fn: Box<dyn Fn(MapPoint<X, Y>, &T, usize) -> bool>) -> impl Iterator<Item = (MapPoint<X, Y>, &'a T, usize)> {
+    pub fn iter_distance<'a>(&'a self, start_point: MapPoint<X, Y>, filter_fn: FilterFn<X, Y, T>) -> impl Iterator<Item = (MapPoint<X, Y>, &'a T, usize)> {
         // use filter_fn as follows (use "_" for unused variables):
         // let filter_fn = Box::new(|point_of_next_cell: MapPoint<X, Y>, value_of_next_cell: &T, current_distance: usize| current_point.use_it_somehow() || current_cell_value.use_it_somehow() || current_distance.use_it_somehow());
         DistanceIter::new(self, start_point, filter_fn)
     }
+    pub fn iter_weighted_distance<F: Fn(MapPoint<X, Y>, &T) -> Option<usize>>(&self, start_point: MapPoint<X, Y>, cost_fn: F) -> impl Iterator<Item = (MapPoint<X, Y>, &T, usize)> {
+        // cost_fn returns cost to enter cell or None, if cell is blocked; cells are returned with nondecreasing accumulated cost
+        WeightedDistanceIter::new(self, start_point, cost_fn)
+    }
+    pub fn distance_field(&self, sources: &[MapPoint<X, Y>], filter_fn: FilterFn<X, Y, T>) -> MyMap2D<usize, X, Y, N> {
+        // multi source breadth first search; filter_fn is used like in iter_distance; unreachable cells keep usize::MAX
+        let mut distances: MyMap2D<usize, X, Y, N> = MyMap2D::init(usize::MAX);
+        let mut next_cells: MyArray<MapPoint<X, Y>, N> = MyArray::new();
+        for source in sources.iter() {
+            if *distances.get(*source) == usize::MAX {
+                distances.set(*source, 0);
+                next_cells.push(*source);
+            }
+        }
+        let mut index = 0;
+        while let Some(current_point) = next_cells.get(index).copied() {
+            let distance = *distances.get(current_point);
+            for (next_cell, value) in self.iter_neighbors(current_point) {
+                if *distances.get(next_cell) == usize::MAX && filter_fn(next_cell, value, distance) {
+                    distances.set(next_cell, distance + 1);
+                    next_cells.push(next_cell);
+                }
+            }
+            index += 1;
+        }
+        distances
+    }
+    pub fn flood_fill<F: Fn(&T) -> bool>(&self, seed: MapPoint<X, Y>, predicate: F) -> Vec<MapPoint<X, Y>> {
+        // 4-connected region of cells satisfying predicate, which includes seed; empty if seed does not satisfy predicate
+        if !predicate(self.get(seed)) {
+            return Vec::new();
+        }
+        let mut visited: MyMap2D<bool, X, Y, N> = MyMap2D::new();
+        let mut region = vec![seed];
+        visited.set(seed, true);
+        let mut index = 0;
+        while let Some(current_point) = region.get(index).copied() {
+            for (next_cell, value) in self.iter_neighbors(current_point) {
+                if !*visited.get(next_cell) && predicate(value) {
+                    visited.set(next_cell, true);
+                    region.push(next_cell);
+                }
+            }
+            index += 1;
+        }
+        region
+    }
+    pub fn shortest_path(&self, start: MapPoint<X, Y>, target: MapPoint<X, Y>, is_cell_free_fn: IsCellFreeFn<X, Y, T>) -> Option<Vec<MapPoint<X, Y>>> {
+        // breadth first search, which records predecessor of each visited cell; start is its own predecessor
+        let mut predecessors: MyMap2D<Option<MapPoint<X, Y>>, X, Y, N> = MyMap2D::new();
+        let mut next_cells: MyArray<MapPoint<X, Y>, N> = MyArray::init(start, 1);
+        predecessors.set(start, Some(start));
+        let mut index = 0;
+        while let Some(current_point) = next_cells.get(index).copied() {
+            if current_point == target {
+                // walk back from target to start
+                let mut path = vec![target];
+                let mut path_point = target;
+                while path_point != start {
+                    path_point = predecessors.get(path_point).unwrap();
+                    path.push(path_point);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (next_cell, _) in current_point.iter_neighbors(Compass::N, true, false, false) {
+                if predecessors.get(next_cell).is_none() && is_cell_free_fn(next_cell, self.get(next_cell)) {
+                    predecessors.set(next_cell, Some(current_point));
+                    next_cells.push(next_cell);
+                }
+            }
+            index += 1;
+        }
+        None
+    }
 }
 
 impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize> Default for MyMap2D<T, X, Y, N> {
@@ -144,16 +322,76 @@ impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize>
     }
 }
 
+impl<T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usize> FromIterator<(MapPoint<X, Y>, T)> for MyMap2D<T, X, Y, N> {
+    fn from_iter<I: IntoIterator<Item = (MapPoint<X, Y>, T)>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for (p, v) in iter {
+            result.set(p, v);
+        }
+        result
+    }
+}
+
+// serde array impls stop at length 32, therefore map is serialized as flat sequence of X * Y items (row by row)
+#[cfg(feature = "serde")]
+impl<T: Serialize, const X: usize, const Y: usize, const N: usize> Serialize for MyMap2D<T, X, Y, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(X * Y))?;
+        for item in self.items.iter().flat_map(|row| row.iter()) {
+            seq.serialize_element(item)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+struct MyMap2DVisitor<T, const X: usize, const Y: usize, const N: usize> {
+    item_type: PhantomData<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Copy + Clone + Default + Deserialize<'de>, const X: usize, const Y: usize, const N: usize> de::Visitor<'de> for MyMap2DVisitor<T, X, Y, N> {
+    type Value = MyMap2D<T, X, Y, N>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a sequence of {} items", X * Y)
+    }
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut map: MyMap2D<T, X, Y, N> = MyMap2D::new();
+        for index in 0..X * Y {
+            match seq.next_element()? {
+                Some(item) => map.items[index / X][index % X] = item,
+                None => return Err(de::Error::invalid_length(index, &self)),
+            }
+        }
+        let mut length = X * Y;
+        while seq.next_element::<de::IgnoredAny>()?.is_some() {
+            length += 1;
+        }
+        if length > X * Y {
+            return Err(de::Error::invalid_length(length, &self));
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Copy + Clone + Default + Deserialize<'de>, const X: usize, const Y: usize, const N: usize> Deserialize<'de> for MyMap2D<T, X, Y, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(MyMap2DVisitor { item_type: PhantomData })
+    }
+}
+
 
 struct DistanceIter<'a, T, const X: usize, const Y: usize, const N: usize> {
     data_map: &'a MyMap2D<T, X, Y, N>,
-    filter_fn: Box<dyn Fn(MapPoint<X, Y>, &T, usize) -> bool>, // input for filter_fn: next possible point, data from data_map of next possible point, distance of current point
+    filter_fn: FilterFn<X, Y, T>, // input for filter_fn: next possible point, data from data_map of next possible point, distance of current point
     next_cells: MyArray<(MapPoint<X, Y>, usize), N>,
     index: usize,
 }
 
 impl<'a, T: Copy + Clone, const X: usize, const Y: usize, const N: usize> DistanceIter<'a, T, X, Y, N> {
-    fn new(data_map: &'a MyMap2D<T, X, Y, N>, start_point: MapPoint<X, Y>, filter_fn: Box<dyn Fn(MapPoint<X, Y>, &T, usize) -> bool>) -> Self {
+    fn new(data_map: &'a MyMap2D<T, X, Y, N>, start_point: MapPoint<X, Y>, filter_fn: FilterFn<X, Y, T>) -> Self {
         DistanceIter {
             data_map,
             filter_fn,
@@ -181,6 +419,54 @@ impl<'a, T: Copy + Clone + Default, const X: usize, const Y: usize, const N: usi
     }
 }
 
+struct WeightedDistanceIter<'a, T, F, const X: usize, const Y: usize, const N: usize> {
+    data_map: &'a MyMap2D<T, X, Y, N>,
+    cost_fn: F,
+    costs: MyMap2D<usize, X, Y, N>, // lowest known accumulated cost of each cell
+    visited: MyMap2D<bool, X, Y, N>,
+    next_cells: BinaryHeap<Reverse<(usize, usize, usize)>>, // accumulated cost, x, y
+}
+
+impl<'a, T: Copy + Clone + Default, F: Fn(MapPoint<X, Y>, &T) -> Option<usize>, const X: usize, const Y: usize, const N: usize> WeightedDistanceIter<'a, T, F, X, Y, N> {
+    fn new(data_map: &'a MyMap2D<T, X, Y, N>, start_point: MapPoint<X, Y>, cost_fn: F) -> Self {
+        let mut costs: MyMap2D<usize, X, Y, N> = MyMap2D::init(usize::MAX);
+        costs.set(start_point, 0);
+        WeightedDistanceIter {
+            data_map,
+            cost_fn,
+            costs,
+            visited: MyMap2D::new(),
+            next_cells: BinaryHeap::from([Reverse((0, start_point.x(), start_point.y()))]),
+        }
+    }
+}
+
+impl<'a, T: Copy + Clone + Default, F: Fn(MapPoint<X, Y>, &T) -> Option<usize>, const X: usize, const Y: usize, const N: usize> Iterator for WeightedDistanceIter<'a, T, F, X, Y, N> {
+    type Item = (MapPoint<X, Y>, &'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(Reverse((cost, x, y))) = self.next_cells.pop() {
+            let map_point = MapPoint::<X, Y>::new(x, y);
+            // cells may be queued multiple times; only first pop has lowest cost
+            if *self.visited.get(map_point) {
+                continue;
+            }
+            self.visited.set(map_point, true);
+            for (next_cell, value) in self.data_map.iter_neighbors(map_point) {
+                if let Some(step_cost) = (self.cost_fn)(next_cell, value) {
+                    let next_cost = cost + step_cost;
+                    if next_cost < *self.costs.get(next_cell) {
+                        self.costs.set(next_cell, next_cost);
+                        self.next_cells.push(Reverse((next_cost, next_cell.x(), next_cell.y())));
+                    }
+                }
+            }
+            return Some((map_point, self.data_map.get(map_point), cost));
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     
@@ -200,6 +486,7 @@ mod tests {
             *cut_off_map.get_mut(p) = game_map.is_cut_off_cell(p, is_cell_free_fn);
         }
         assert_eq!(cut_off_map.iter().filter(|(_, c)| **c == true).count(), 5);
+        assert_eq!(cut_off_map.count_where(|_, c| *c), 5);
         
         game_map.set(MapPoint::<X, Y>::new(8, 2), false);
         for (p, _) in game_map.iter().filter(|(_, c)| **c) {
@@ -207,6 +494,7 @@ mod tests {
             *cut_off_map.get_mut(p) = game_map.is_cut_off_cell(p, is_cell_free_fn);
         }
         assert_eq!(cut_off_map.iter().filter(|(_, c)| **c == true).count(), 5);
+        assert_eq!(cut_off_map.count_where(|_, c| *c), 5);
 
         game_map.set(MapPoint::<X, Y>::new(7, 4), false);
         for (p, _) in game_map.iter().filter(|(_, c)| **c) {
@@ -214,6 +502,7 @@ mod tests {
             *cut_off_map.get_mut(p) = game_map.is_cut_off_cell(p, is_cell_free_fn);
         }
         assert_eq!(cut_off_map.iter().filter(|(_, c)| **c == true).count(), 7);
+        assert_eq!(cut_off_map.count_where(|_, c| *c), 7);
 
         game_map.set(MapPoint::<X, Y>::new(7, 6), false);
         for (p, _) in game_map.iter().filter(|(_, c)| **c) {
@@ -221,6 +510,7 @@ mod tests {
             *cut_off_map.get_mut(p) = game_map.is_cut_off_cell(p, is_cell_free_fn);
         }
         assert_eq!(cut_off_map.iter().filter(|(_, c)| **c == true).count(), 10);
+        assert_eq!(cut_off_map.count_where(|_, c| *c), 10);
 
         game_map.set(MapPoint::<X, Y>::new(9, 8), false);
         for (p, _) in game_map.iter().filter(|(_, c)| **c) {
@@ -228,6 +518,350 @@ mod tests {
             *cut_off_map.get_mut(p) = game_map.is_cut_off_cell(p, is_cell_free_fn);
         }
         assert_eq!(cut_off_map.iter().filter(|(_, c)| **c == true).count(), 14);
+        assert_eq!(cut_off_map.count_where(|_, c| *c), 14);
         assert!(*cut_off_map.get(MapPoint::<X, Y>::new(8, 7)));
+        assert!(cut_off_map.any_where(|p, c| *c && p == MapPoint::<X, Y>::new(8, 7)));
+        assert!(!cut_off_map.any_where(|p, c| *c && p == MapPoint::<X, Y>::new(15, 5)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_neighbor_values() {
+        const X: usize = 4;
+        const Y: usize = 3;
+        const N: usize = X * Y;
+
+        let mut map: MyMap2D<usize, X, Y, N> = MyMap2D::new();
+        for (p, v) in map.iter_mut() {
+            *v = p.y() * X + p.x();
+        }
+        for center_point in [MapPoint::<X, Y>::new(0, 0), MapPoint::<X, Y>::new(1, 1), MapPoint::<X, Y>::new(3, 2)] {
+            let expected: Vec<usize> = map.iter_neighbors(center_point).map(|(_, v)| *v).collect();
+            let values: Vec<usize> = map.neighbor_values(center_point).copied().collect();
+            assert_eq!(values, expected);
+        }
+        assert_eq!(map.neighbor_values(MapPoint::<X, Y>::new(1, 1)).copied().collect::<Vec<usize>>(), vec![1, 6, 9, 4]);
+    }
+
+    #[test]
+    fn test_neighbors_count_where() {
+        const X: usize = 4;
+        const Y: usize = 4;
+        const N: usize = X * Y;
+
+        // live cells: (0, 0), (1, 0), (2, 1), (0, 2), (1, 2)
+        let mut map: MyMap2D<bool, X, Y, N> = MyMap2D::new();
+        for (x, y) in [(0, 0), (1, 0), (2, 1), (0, 2), (1, 2)] {
+            map.set(MapPoint::<X, Y>::new(x, y), true);
+        }
+        // interior cell
+        let interior = MapPoint::<X, Y>::new(1, 1);
+        assert_eq!(map.neighbors_count_where(interior, true, |c| *c), 5);
+        assert_eq!(map.neighbors_count_where(interior, false, |c| *c), 3);
+        assert_eq!(map.neighbors_count_where(interior, true, |c| !*c), 3);
+        // edge cell
+        let edge = MapPoint::<X, Y>::new(0, 1);
+        assert_eq!(map.neighbors_count_where(edge, true, |c| *c), 4);
+        assert_eq!(map.neighbors_count_where(edge, false, |c| *c), 2);
+    }
+
+    #[test]
+    fn test_step() {
+        const X: usize = 5;
+        const Y: usize = 5;
+        const N: usize = X * Y;
+
+        // Conway's game of life with blinker oscillating between horizontal and vertical line
+        let life = |_: MapPoint<X, Y>, alive: &bool, alive_neighbors: usize| alive_neighbors == 3 || (*alive && alive_neighbors == 2);
+        let mut horizontal: MyMap2D<bool, X, Y, N> = MyMap2D::new();
+        let mut vertical: MyMap2D<bool, X, Y, N> = MyMap2D::new();
+        for i in 1..4 {
+            horizontal.set(MapPoint::<X, Y>::new(i, 2), true);
+            vertical.set(MapPoint::<X, Y>::new(2, i), true);
+        }
+        let first_step = horizontal.step(true, |c| *c, life);
+        assert!(first_step == vertical);
+        let second_step = first_step.step(true, |c| *c, life);
+        assert!(second_step == horizontal);
+        assert_eq!(second_step.count_where(|_, c| *c), 3);
+    }
+
+    #[test]
+    fn test_get_set_checked() {
+        const X: usize = 4;
+        const Y: usize = 3;
+        const N: usize = X * Y;
+
+        let mut map: MyMap2D<u8, X, Y, N> = MyMap2D::new();
+        assert_eq!(map.set_checked(3, 2, 5), Some(&5));
+        assert_eq!(map.get_checked(3, 2), Some(&5));
+        assert_eq!(*map.get(MapPoint::<X, Y>::new(3, 2)), 5);
+        assert_eq!(map.get_checked(0, 0), Some(&0));
+        for (x, y) in [(X, 0), (0, Y), (X, Y), (usize::MAX, usize::MAX)] {
+            assert_eq!(map.get_checked(x, y), None);
+            assert_eq!(map.set_checked(x, y, 7), None);
+        }
+        assert!(map.iter().all(|(_, v)| *v != 7));
+    }
+
+    #[test]
+    fn test_map() {
+        const X: usize = 4;
+        const Y: usize = 3;
+        const N: usize = X * Y;
+
+        let mut grid: MyMap2D<u8, X, Y, N> = MyMap2D::new();
+        for (p, v) in grid.iter_mut() {
+            *v = (p.y() * X + p.x()) as u8;
+        }
+        let parity: MyMap2D<bool, X, Y, N> = grid.map(|_, v| v % 2 == 1);
+        assert!(!*parity.get(MapPoint::<X, Y>::new(0, 0)));
+        assert!(*parity.get(MapPoint::<X, Y>::new(1, 0)));
+        assert!(*parity.get(MapPoint::<X, Y>::new(3, 2)));
+        assert!(!*parity.get(MapPoint::<X, Y>::new(2, 1)));
+        assert_eq!(parity.iter().filter(|(_, v)| **v).count(), 6);
+        // closure gets map point of cell
+        let columns: MyMap2D<usize, X, Y, N> = grid.map(|p, _| p.x());
+        assert!(columns.iter().all(|(p, v)| p.x() == *v));
+    }
+
+    #[test]
+    fn test_iter_row_column_mut() {
+        const X: usize = 4;
+        const Y: usize = 3;
+        const N: usize = X * Y;
+
+        let mut map: MyMap2D<u8, X, Y, N> = MyMap2D::new();
+        for (p, v) in map.iter_row_mut(1) {
+            assert_eq!(p.y(), 1);
+            *v = 1;
+        }
+        assert_eq!(map.iter().filter(|(_, v)| **v == 1).count(), X);
+        assert!(map.iter().all(|(p, v)| (*v == 1) == (p.y() == 1)));
+
+        for (p, v) in map.iter_column_mut(2) {
+            assert_eq!(p.x(), 2);
+            *v += 2;
+        }
+        assert_eq!(map.iter_column(2).map(|(_, v)| *v).collect::<Vec<u8>>(), vec![2, 3, 2]);
+        assert_eq!(map.iter_row(0).map(|(_, v)| *v).collect::<Vec<u8>>(), vec![0, 0, 2, 0]);
+        assert_eq!(map.iter_row(1).map(|(_, v)| *v).collect::<Vec<u8>>(), vec![1, 1, 3, 1]);
+        assert_eq!(map.iter_row(2).map(|(_, v)| *v).collect::<Vec<u8>>(), vec![0, 0, 2, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "row index is out of range")]
+    fn test_iter_row_mut_out_of_range() {
+        let mut map: MyMap2D<u8, 4, 3, 12> = MyMap2D::new();
+        map.iter_row_mut(3).for_each(|(_, v)| *v = 1);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        const X: usize = 4;
+        const Y: usize = 3;
+        const N: usize = X * Y;
+
+        let mut grid: MyMap2D<u8, X, Y, N> = MyMap2D::new();
+        for (p, v) in grid.iter_mut() {
+            *v = (p.y() * X + p.x()) as u8;
+        }
+        let round_trip = grid.iter().map(|(p, v)| (p, *v)).collect::<MyMap2D<_, X, Y, N>>();
+        assert!(round_trip == grid);
+
+        let points = [(MapPoint::<X, Y>::new(1, 1), 3), (MapPoint::<X, Y>::new(2, 0), 5), (MapPoint::<X, Y>::new(1, 1), 7)];
+        let sparse: MyMap2D<u8, X, Y, N> = points.into_iter().collect();
+        assert_eq!(*sparse.get(MapPoint::<X, Y>::new(1, 1)), 7);
+        assert_eq!(*sparse.get(MapPoint::<X, Y>::new(2, 0)), 5);
+        assert_eq!(sparse.count_where(|_, v| *v == 0), N - 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        const X: usize = 20;
+        const Y: usize = 10;
+        const N: usize = X * Y;
+
+        let mut map: MyMap2D<bool, X, Y, N> = MyMap2D::new();
+        map.set(MapPoint::<X, Y>::new(0, 0), true);
+        map.set(MapPoint::<X, Y>::new(19, 0), true);
+        map.set(MapPoint::<X, Y>::new(7, 4), true);
+        map.set(MapPoint::<X, Y>::new(19, 9), true);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json.matches("true").count(), 4);
+        assert_eq!(json.matches("false").count(), N - 4);
+        let loaded: MyMap2D<bool, X, Y, N> = serde_json::from_str(&json).unwrap();
+        assert!(loaded == map);
+
+        // length of sequence must be X * Y
+        let too_short = serde_json::to_string(&vec![false; N - 1]).unwrap();
+        assert!(serde_json::from_str::<MyMap2D<bool, X, Y, N>>(&too_short).is_err());
+        let too_long = serde_json::to_string(&vec![false; N + 1]).unwrap();
+        assert!(serde_json::from_str::<MyMap2D<bool, X, Y, N>>(&too_long).is_err());
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        const X: usize = 5;
+        const Y: usize = 4;
+        const N: usize = X * Y;
+
+        // wall in column 2 with gap in last row
+        let mut game_map: MyMap2D<bool, X, Y, N> = MyMap2D::init(true);
+        for y in 0..Y - 1 {
+            game_map.set(MapPoint::<X, Y>::new(2, y), false);
+        }
+        let start = MapPoint::<X, Y>::new(0, 0);
+        let target = MapPoint::<X, Y>::new(4, 0);
+        let path = game_map.shortest_path(start, target, Box::new(|_, c: &bool| *c)).unwrap();
+        assert_eq!(path.len(), 11);
+        assert_eq!(path[0], start);
+        assert_eq!(path[10], target);
+        assert!(path.contains(&MapPoint::<X, Y>::new(2, 3)));
+        assert!(path.windows(2).all(|w| w[0].distance(w[1]) == 1));
+        assert!(path.iter().all(|p| *game_map.get(*p)));
+
+        // path to start contains only start
+        assert_eq!(game_map.shortest_path(start, start, Box::new(|_, c: &bool| *c)), Some(vec![start]));
+
+        // closing gap makes target unreachable
+        game_map.set(MapPoint::<X, Y>::new(2, 3), false);
+        assert_eq!(game_map.shortest_path(start, target, Box::new(|_, c: &bool| *c)), None);
+    }
+
+    #[test]
+    fn test_distance_field() {
+        const X: usize = 5;
+        const Y: usize = 3;
+        const N: usize = X * Y;
+
+        // wall in column 1 except last row
+        let mut game_map: MyMap2D<bool, X, Y, N> = MyMap2D::init(true);
+        game_map.set(MapPoint::<X, Y>::new(1, 0), false);
+        game_map.set(MapPoint::<X, Y>::new(1, 1), false);
+        let sources = [MapPoint::<X, Y>::new(0, 0), MapPoint::<X, Y>::new(4, 0)];
+        let distances = game_map.distance_field(&sources, Box::new(|_, c: &bool, _| *c));
+        assert_eq!(*distances.get(MapPoint::<X, Y>::new(0, 0)), 0);
+        assert_eq!(*distances.get(MapPoint::<X, Y>::new(4, 0)), 0);
+        // each cell takes minimum distance to any source
+        assert_eq!(*distances.get(MapPoint::<X, Y>::new(2, 0)), 2);
+        assert_eq!(*distances.get(MapPoint::<X, Y>::new(1, 2)), 3);
+        assert_eq!(*distances.get(MapPoint::<X, Y>::new(0, 2)), 2);
+        assert_eq!(*distances.get(MapPoint::<X, Y>::new(2, 2)), 4);
+        // walls are unreachable
+        assert_eq!(*distances.get(MapPoint::<X, Y>::new(1, 0)), usize::MAX);
+        assert_eq!(*distances.get(MapPoint::<X, Y>::new(1, 1)), usize::MAX);
+
+        // single source equals distances of iter_distance
+        let distances = game_map.distance_field(&sources[..1], Box::new(|_, c: &bool, _| *c));
+        for (point, _, distance) in game_map.iter_distance(sources[0], Box::new(|_, c: &bool, _| *c)) {
+            assert_eq!(*distances.get(point), distance);
+        }
+    }
+
+    #[test]
+    fn test_iter_weighted_distance() {
+        const X: usize = 5;
+        const Y: usize = 4;
+        const N: usize = X * Y;
+
+        // cost to enter cell; row 1 is expensive barrier except column 4
+        let mut terrain: MyMap2D<usize, X, Y, N> = MyMap2D::init(1);
+        for x in 0..X - 1 {
+            terrain.set(MapPoint::<X, Y>::new(x, 1), 10);
+        }
+        let start = MapPoint::<X, Y>::new(0, 0);
+        let distances: Vec<(MapPoint<X, Y>, usize)> = terrain.iter_weighted_distance(start, |_, c| Some(*c)).map(|(p, _, d)| (p, d)).collect();
+        assert_eq!(distances.len(), N);
+        assert!(distances.windows(2).all(|w| w[0].1 <= w[1].1));
+        let distance_of = |x: usize, y: usize| distances.iter().find(|(p, _)| *p == MapPoint::<X, Y>::new(x, y)).unwrap().1;
+        assert_eq!(distance_of(0, 0), 0);
+        // cheaper detour through column 4 instead of crossing barrier
+        assert_eq!(distance_of(4, 2), 6);
+        // direct crossing of barrier would cost 11
+        assert_eq!(distance_of(0, 2), 10);
+        assert_eq!(distance_of(0, 3), 11);
+        assert_eq!(distance_of(3, 2), 7);
+
+        // blocked barrier cells are not returned
+        let blocked: Vec<MapPoint<X, Y>> = terrain.iter_weighted_distance(start, |_, c| if *c == 10 { None } else { Some(*c) }).map(|(p, _, _)| p).collect();
+        assert_eq!(blocked.len(), N - (X - 1));
+        assert!(!blocked.contains(&MapPoint::<X, Y>::new(2, 1)));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        const X: usize = 4;
+        const Y: usize = 3;
+        const N: usize = X * Y;
+
+        // two regions of true cells separated by column 2
+        let mut game_map: MyMap2D<bool, X, Y, N> = MyMap2D::init(true);
+        for y in 0..Y {
+            game_map.set(MapPoint::<X, Y>::new(2, y), false);
+        }
+        let region = game_map.flood_fill(MapPoint::<X, Y>::new(0, 1), |c| *c);
+        assert_eq!(region.len(), 6);
+        assert!(region.iter().all(|p| p.x() < 2));
+        let region = game_map.flood_fill(MapPoint::<X, Y>::new(3, 2), |c| *c);
+        assert_eq!(region.len(), 3);
+        assert!(region.iter().all(|p| p.x() == 3));
+        // seed not satisfying predicate returns empty region
+        assert!(game_map.flood_fill(MapPoint::<X, Y>::new(2, 0), |c| *c).is_empty());
+        // region of false cells
+        assert_eq!(game_map.flood_fill(MapPoint::<X, Y>::new(2, 0), |c| !*c).len(), 3);
+    }
+
+    #[test]
+    fn test_iter_neighbors_wrapping() {
+        const X: usize = 4;
+        const Y: usize = 3;
+        const N: usize = X * Y;
+
+        let mut map: MyMap2D<usize, X, Y, N> = MyMap2D::new();
+        for (p, v) in map.iter_mut() {
+            *v = p.y() * X + p.x();
+        }
+        let values: Vec<usize> = map.iter_neighbors_wrapping(MapPoint::<X, Y>::new(0, 0)).map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![8, 1, 4, 3]);
+        assert!(map.iter_neighbors_wrapping(MapPoint::<X, Y>::new(1, 1)).eq(map.iter_neighbors(MapPoint::<X, Y>::new(1, 1))));
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let mut map: MyMap2D<usize, 3, 2, 6> = MyMap2D::new();
+        for (p, v) in map.iter_mut() {
+            *v = p.y() * 3 + p.x();
+        }
+        let transposed = map.transpose();
+        assert!(transposed.iter().all(|(p, v)| *v == p.x() * 3 + p.y()));
+        assert!(transposed.transpose() == map);
+
+        let mut board: MyMap2D<usize, 3, 3, 9> = MyMap2D::new();
+        for (p, v) in board.iter_mut() {
+            *v = p.y() * 3 + p.x();
+        }
+        let rotated = board.rotate_90_cw();
+        // top left corner moves to top right corner
+        assert_eq!(*rotated.get(MapPoint::<3, 3>::new(2, 0)), 0);
+        assert_eq!(rotated.iter_row(0).map(|(_, v)| *v).collect::<Vec<usize>>(), vec![6, 3, 0]);
+        assert!(rotated.rotate_90_ccw() == board);
+        let mut rotated = board;
+        for _ in 0..4 {
+            rotated = rotated.rotate_90_cw();
+        }
+        assert!(rotated == board);
+        for _ in 0..4 {
+            rotated = rotated.rotate_90_ccw();
+        }
+        assert!(rotated == board);
+        assert_eq!(*board.get(MapPoint::<3, 3>::new(0, 0)), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "rotation requires square map")]
+    fn test_rotate_non_square_map() {
+        let map: MyMap2D<u8, 3, 2, 6> = MyMap2D::new();
+        map.rotate_90_cw();
+    }
+}
@@ -49,6 +49,12 @@ impl<const X: usize, const Y: usize> MapPoint<X, Y> {
     pub fn distance(&self, target: MapPoint<X, Y>) -> usize {
         self.distance_x(target) + self.distance_y(target)
     }
+    pub fn manhattan_distance(&self, other: MapPoint<X, Y>) -> usize {
+        self.distance(other)
+    }
+    pub fn chebyshev_distance(&self, other: MapPoint<X, Y>) -> usize {
+        self.distance_x(other).max(self.distance_y(other))
+    }
     pub fn delta_xy(&self, target: MapPoint<X, Y>) -> usize {
         let dist_x = self.distance_x(target);
         let dist_y = self.distance_y(target);
@@ -153,6 +159,22 @@ impl<const X: usize, const Y: usize> MapPoint<X, Y> {
             None
         }
     }
+    pub fn offset_checked(&self, dx: isize, dy: isize) -> Option<MapPoint<X, Y>> {
+        let x = self.x.checked_add_signed(dx)?;
+        let y = self.y.checked_add_signed(dy)?;
+        let result = MapPoint { x, y };
+        if result.is_in_map() {
+            Some(result)
+        } else {
+            None
+        }
+    }
+    pub fn offset_saturating(&self, dx: isize, dy: isize) -> MapPoint<X, Y> {
+        MapPoint {
+            x: self.x.saturating_add_signed(dx).min(X - 1),
+            y: self.y.saturating_add_signed(dy).min(Y - 1),
+        }
+    }
     pub fn neighbor(&self, orientation: Compass) -> Option<MapPoint<X, Y>> {
         match orientation {
             Compass::Center => Some(*self),
@@ -166,15 +188,40 @@ impl<const X: usize, const Y: usize> MapPoint<X, Y> {
             Compass::NW => self.offset_mm((1, 1)),
         }
     }
+    pub fn neighbor_wrapping(&self, orientation: Compass) -> MapPoint<X, Y> {
+        // map is handled as torus: leaving map at one side results in entering map at opposite side
+        let west = (self.x + X - 1) % X;
+        let east = (self.x + 1) % X;
+        let north = (self.y + Y - 1) % Y;
+        let south = (self.y + 1) % Y;
+        let (x, y) = match orientation {
+            Compass::Center => (self.x, self.y),
+            Compass::N => (self.x, north),
+            Compass::NE => (east, north),
+            Compass::E => (east, self.y),
+            Compass::SE => (east, south),
+            Compass::S => (self.x, south),
+            Compass::SW => (west, south),
+            Compass::W => (west, self.y),
+            Compass::NW => (west, north),
+        };
+        MapPoint { x, y }
+    }
     pub fn orientation_of_neighbor(&self, neighbor: MapPoint<X, Y>) -> Option<Compass> {
         self.iter_neighbors(Compass::N, true, false, true).find(|(p, _)| *p == neighbor).map_or(None, |(_, o)| Some(o))
     }
     pub fn iter_neighbors(&self, initial_orientation: Compass, rotation_direction: bool, include_center: bool, include_corners: bool) -> impl Iterator<Item = (MapPoint<X, Y>, Compass)> {
-        NeighborIter::new(*self, initial_orientation, rotation_direction, include_center, include_corners)
+        NeighborIter::new(*self, initial_orientation, rotation_direction, include_center, include_corners, false)
+    }
+    pub fn iter_neighbors_wrapping(&self, initial_orientation: Compass, rotation_direction: bool, include_center: bool, include_corners: bool) -> impl Iterator<Item = (MapPoint<X, Y>, Compass)> {
+        NeighborIter::new(*self, initial_orientation, rotation_direction, include_center, include_corners, true)
     }
     pub fn iter_orientation(&self, orientation: Compass) -> impl Iterator<Item = MapPoint<X, Y>> {
         OrientationIter::new(*self, orientation)
     }
+    pub fn iter_line_to(&self, target: MapPoint<X, Y>) -> impl Iterator<Item = MapPoint<X, Y>> {
+        LineIter::new(*self, target)
+    }
 }
 
 struct NeighborIter<const X: usize, const Y: usize> {
@@ -184,11 +231,12 @@ struct NeighborIter<const X: usize, const Y: usize> {
     initial_orientation: Compass,
     current_orientation: Compass,
     rotation_direction: bool,
+    wrapping: bool,
     finished: bool,
 }
 
 impl<const X: usize, const Y: usize>NeighborIter<X, Y> {
-    fn new(center_point: MapPoint<X, Y>, initial_orientation: Compass, rotation_direction: bool, include_center: bool, include_corners: bool) -> Self {
+    fn new(center_point: MapPoint<X, Y>, initial_orientation: Compass, rotation_direction: bool, include_center: bool, include_corners: bool, wrapping: bool) -> Self {
         if initial_orientation.is_center() {
             panic!("line {}, need direction", line!());
         }
@@ -202,6 +250,7 @@ impl<const X: usize, const Y: usize>NeighborIter<X, Y> {
             initial_orientation,
             current_orientation: initial_orientation,
             rotation_direction,
+            wrapping,
             finished: false,
         }
     }
@@ -235,6 +284,8 @@ impl<const X: usize, const Y: usize> Iterator for NeighborIter<X, Y> {
         while !self.finished {
             let result = if self.include_center {
                 Some((self.center_point, Compass::Center))
+            } else if self.wrapping {
+                Some((self.center_point.neighbor_wrapping(self.current_orientation), self.current_orientation))
             } else {
                 self.center_point.neighbor(self.current_orientation).map_or(None, |n| Some((n, self.current_orientation)))
             };
@@ -285,6 +336,57 @@ impl<const X: usize, const Y: usize> Iterator for OrientationIter<X, Y> {
     }
 }
 
+struct LineIter<const X: usize, const Y: usize> {
+    // Bresenham line from start point to target, both included
+    current: (isize, isize),
+    target: (isize, isize),
+    delta: (isize, isize), // dx >= 0, dy <= 0
+    step: (isize, isize),
+    error: isize,
+    finished: bool,
+}
+
+impl <const X: usize, const Y: usize>LineIter<X, Y> {
+    fn new(start_point: MapPoint<X, Y>, target: MapPoint<X, Y>) -> Self {
+        let current = (start_point.x() as isize, start_point.y() as isize);
+        let target = (target.x() as isize, target.y() as isize);
+        let delta = ((target.0 - current.0).abs(), -(target.1 - current.1).abs());
+        LineIter {
+            current,
+            target,
+            delta,
+            step: ((target.0 - current.0).signum(), (target.1 - current.1).signum()),
+            error: delta.0 + delta.1,
+            finished: false,
+        }
+    }
+}
+
+impl<const X: usize, const Y: usize> Iterator for LineIter<X, Y> {
+    type Item = MapPoint<X, Y>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None
+        }
+        let result = MapPoint::<X, Y>::new(self.current.0 as usize, self.current.1 as usize);
+        if self.current == self.target {
+            self.finished = true;
+            return Some(result);
+        }
+        let double_error = 2 * self.error;
+        if double_error >= self.delta.1 {
+            self.error += self.delta.1;
+            self.current.0 += self.step.0;
+        }
+        if double_error <= self.delta.0 {
+            self.error += self.delta.0;
+            self.current.1 += self.step.1;
+        }
+        Some(result)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     
@@ -338,4 +440,97 @@ mod tests {
         let a = MapPoint::<X, Y>::new(18, 8);
         assert!(a.map_position().is_center());
     }
+
+    #[test]
+    fn test_wrapping_neighbors() {
+        const X: usize = 20;
+        const Y: usize = 10;
+        let a = MapPoint::<X, Y>::new(0, 0);
+        assert_eq!(a.neighbor_wrapping(Compass::W), MapPoint::<X, Y>::new(X - 1, 0));
+        assert_eq!(a.neighbor_wrapping(Compass::N), MapPoint::<X, Y>::new(0, Y - 1));
+        assert_eq!(a.neighbor_wrapping(Compass::NW), MapPoint::<X, Y>::new(X - 1, Y - 1));
+        let neighbors: Vec<(MapPoint<X, Y>, Compass)> = a.iter_neighbors_wrapping(Compass::N, true, false, false).collect();
+        assert!(neighbors == vec![(MapPoint::<X, Y>::new(0, Y - 1), Compass::N), (MapPoint::<X, Y>::new(1, 0), Compass::E), (MapPoint::<X, Y>::new(0, 1), Compass::S), (MapPoint::<X, Y>::new(X - 1, 0), Compass::W)]);
+        // corner and side classification is same as without wrapping
+        let b = MapPoint::<X, Y>::new(5, 5);
+        assert!(b.iter_neighbors_wrapping(Compass::N, true, true, true).eq(b.iter_neighbors(Compass::N, true, true, true)));
+        assert_eq!(a.iter_neighbors_wrapping(Compass::N, true, false, true).filter(|(_, o)| o.is_ordinal()).count(), 4);
+        assert_eq!(a.iter_neighbors(Compass::N, true, false, true).count(), 3);
+    }
+
+    #[test]
+    fn test_distance_metrics() {
+        const X: usize = 20;
+        const Y: usize = 10;
+        let a = MapPoint::<X, Y>::new(5, 5);
+        assert_eq!(a.manhattan_distance(a), 0);
+        assert_eq!(a.chebyshev_distance(a), 0);
+        // cardinal
+        let b = MapPoint::<X, Y>::new(5, 2);
+        assert_eq!(a.manhattan_distance(b), 3);
+        assert_eq!(a.chebyshev_distance(b), 3);
+        let b = MapPoint::<X, Y>::new(9, 5);
+        assert_eq!(b.manhattan_distance(a), 4);
+        assert_eq!(b.chebyshev_distance(a), 4);
+        // diagonal
+        let c = MapPoint::<X, Y>::new(8, 8);
+        assert_eq!(a.manhattan_distance(c), 6);
+        assert_eq!(a.chebyshev_distance(c), 3);
+        let d = MapPoint::<X, Y>::new(0, 9);
+        assert_eq!(a.manhattan_distance(d), 9);
+        assert_eq!(d.chebyshev_distance(a), 5);
+    }
+
+    #[test]
+    fn test_iter_line_to() {
+        const X: usize = 10;
+        const Y: usize = 10;
+        // horizontal
+        let a = MapPoint::<X, Y>::new(2, 3);
+        let b = MapPoint::<X, Y>::new(6, 3);
+        let line: Vec<MapPoint<X, Y>> = a.iter_line_to(b).collect();
+        assert_eq!(line.len(), 5);
+        assert_eq!(line[0], a);
+        assert_eq!(line[4], b);
+        assert!(line.iter().all(|p| p.y() == 3));
+        // vertical and backwards
+        let c = MapPoint::<X, Y>::new(2, 0);
+        let line: Vec<MapPoint<X, Y>> = a.iter_line_to(c).collect();
+        assert_eq!(line, vec![a, MapPoint::<X, Y>::new(2, 2), MapPoint::<X, Y>::new(2, 1), c]);
+        // diagonal
+        let d = MapPoint::<X, Y>::new(5, 6);
+        let line: Vec<MapPoint<X, Y>> = a.iter_line_to(d).collect();
+        assert_eq!(line.len(), 4);
+        assert!(line.windows(2).all(|w| w[0].distance_x(w[1]) == 1 && w[0].distance_y(w[1]) == 1));
+        // diagonal-ish: number of steps equals chebyshev distance
+        let e = MapPoint::<X, Y>::new(9, 0);
+        let f = MapPoint::<X, Y>::new(0, 4);
+        let line: Vec<MapPoint<X, Y>> = e.iter_line_to(f).collect();
+        assert_eq!(line.len(), e.chebyshev_distance(f) + 1);
+        assert_eq!(line[0], e);
+        assert_eq!(*line.last().unwrap(), f);
+        assert!(line.windows(2).all(|w| w[0].chebyshev_distance(w[1]) == 1));
+        // line to itself contains only start point
+        assert_eq!(a.iter_line_to(a).collect::<Vec<MapPoint<X, Y>>>(), vec![a]);
+    }
+    #[test]
+    fn test_offset() {
+        const X: usize = 8;
+        const Y: usize = 6;
+        let top_left = MapPoint::<X, Y>::new(0, 0);
+        let bottom_right = MapPoint::<X, Y>::new(X - 1, Y - 1);
+        // knight moves
+        assert_eq!(top_left.offset_checked(1, 2), Some(MapPoint::<X, Y>::new(1, 2)));
+        assert_eq!(bottom_right.offset_checked(-2, -1), Some(MapPoint::<X, Y>::new(5, 4)));
+        assert_eq!(top_left.offset_checked(-1, 2), None);
+        assert_eq!(top_left.offset_checked(2, -1), None);
+        assert_eq!(bottom_right.offset_checked(1, -2), None);
+        assert_eq!(bottom_right.offset_checked(-1, 2), None);
+        // saturating clamps to border
+        assert_eq!(top_left.offset_saturating(-3, 2), MapPoint::<X, Y>::new(0, 2));
+        assert_eq!(top_left.offset_saturating(-3, -3), top_left);
+        assert_eq!(bottom_right.offset_saturating(5, -2), MapPoint::<X, Y>::new(X - 1, Y - 3));
+        assert_eq!(bottom_right.offset_saturating(isize::MAX, isize::MAX), bottom_right);
+        assert_eq!(bottom_right.offset_saturating(-2, -1), bottom_right.offset_checked(-2, -1).unwrap());
+    }
 }
\ No newline at end of file